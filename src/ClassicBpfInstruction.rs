@@ -0,0 +1,73 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A classic Berkeley Packet Filter (cBPF) instruction, as used by `SO_ATTACH_FILTER` and `SO_ATTACH_REUSEPORT_CBPF`.
+///
+/// Identical in layout to Linux's `struct sock_filter`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClassicBpfInstruction
+{
+	/// Operation code.
+	pub code: u16,
+
+	/// Relative jump if true.
+	pub jt: u8,
+
+	/// Relative jump if false.
+	pub jf: u8,
+
+	/// Generic multi-use field, eg a constant.
+	pub k: u32,
+}
+
+impl ClassicBpfInstruction
+{
+	/// `ld #cpu`: loads the logical core processing the packet into the accumulator.
+	#[inline(always)]
+	pub fn load_logical_core() -> Self
+	{
+		Self::new(BPF_LD | BPF_W | BPF_ABS, 0, 0, (SKF_AD_OFF + SKF_AD_CPU) as u32)
+	}
+
+	/// `sub #constant`: subtracts a constant from the accumulator.
+	#[inline(always)]
+	pub fn subtract(constant: u32) -> Self
+	{
+		Self::new(BPF_ALU | BPF_SUB | BPF_K, 0, 0, constant)
+	}
+
+	/// `jeq #constant, jump_if_true, jump_if_false`: jumps forward depending on whether the accumulator is equal to a constant.
+	#[inline(always)]
+	pub fn jump_if_equal(constant: u32, jump_if_true: u8, jump_if_false: u8) -> Self
+	{
+		Self::new(BPF_JMP | BPF_JEQ | BPF_K, jump_if_true, jump_if_false, constant)
+	}
+
+	/// `ret a`: returns the accumulator.
+	#[inline(always)]
+	pub fn return_accumulator() -> Self
+	{
+		Self::new(BPF_RET | BPF_A, 0, 0, 0)
+	}
+
+	/// `ret #constant`: returns a constant.
+	#[inline(always)]
+	pub fn return_constant(constant: u32) -> Self
+	{
+		Self::new(BPF_RET | BPF_K, 0, 0, constant)
+	}
+
+	#[inline(always)]
+	const fn new(code: u16, jt: u8, jf: u8, k: u32) -> Self
+	{
+		Self
+		{
+			code,
+			jt,
+			jf,
+			k,
+		}
+	}
+}
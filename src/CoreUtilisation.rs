@@ -0,0 +1,201 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A sample of the cumulative times each online logical core has spent in various states, from `/proc/stat`.
+///
+/// Take two samples some time apart and use `since()` to find utilisation over the interval, or `least_loaded()` to choose logical cores for new workers.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CoreUtilisation(PerLogicalCoreData<CpuTimes>);
+
+impl Deref for CoreUtilisation
+{
+	type Target = PerLogicalCoreData<CpuTimes>;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		&self.0
+	}
+}
+
+impl From<CoreUtilisation> for PerLogicalCoreData<CpuTimes>
+{
+	#[inline(always)]
+	fn from(core_utilisation: CoreUtilisation) -> Self
+	{
+		core_utilisation.0
+	}
+}
+
+impl CoreUtilisation
+{
+	/// Samples `/proc/stat`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn sample() -> io::Result<Self>
+	{
+		Self::parse_proc_stat(&read_to_string("/proc/stat")?)
+	}
+
+	/// Parses the per logical core `cpuN` lines of the contents of `/proc/stat`; other lines, including the aggregate `cpu` line, are ignored.
+	///
+	/// Columns added in later versions of Linux (eg `steal`) are zero if absent.
+	///
+	/// Fails with `InvalidData` if there are no `cpuN` lines.
+	pub fn parse_proc_stat(proc_stat: &str) -> io::Result<Self>
+	{
+		let mut cpu_times = Vec::new();
+		for line in proc_stat.lines()
+		{
+			let mut fields = line.split_whitespace();
+
+			let logical_core_identifier = match fields.next()
+			{
+				Some(name) => match name.strip_prefix("cpu")
+				{
+					Some(logical_core) if !logical_core.is_empty() => LogicalCoreIdentifier::from_str(logical_core).map_err(|_| LogicalCores::invalid_data(format!("Invalid logical core in '{}'", name)))?,
+					_ => continue,
+				},
+				None => continue,
+			};
+
+			let mut columns = [0u64; 10];
+			for (index, field) in fields.take(columns.len()).enumerate()
+			{
+				columns[index] = u64::from_str(field).map_err(|_| LogicalCores::invalid_data(format!("Invalid time '{}' for logical core {}", field, logical_core_identifier)))?;
+			}
+
+			cpu_times.push
+			((
+				logical_core_identifier,
+				CpuTimes
+				{
+					user: columns[0],
+					nice: columns[1],
+					system: columns[2],
+					idle: columns[3],
+					iowait: columns[4],
+					irq: columns[5],
+					softirq: columns[6],
+					steal: columns[7],
+					guest: columns[8],
+					guest_nice: columns[9],
+				}
+			));
+		}
+
+		if unlikely!(cpu_times.is_empty())
+		{
+			return Err(LogicalCores::invalid_data("No per logical core lines".to_string()))
+		}
+
+		Ok(CoreUtilisation(cpu_times.into_iter().collect()))
+	}
+
+	/// The times of each logical core since `previous`, which should be an earlier sample.
+	///
+	/// Logical cores not in both samples (eg because they were taken offline) are omitted, so the result may have no data at all.
+	#[inline(always)]
+	pub fn since(&self, previous: &Self) -> PerLogicalCoreData<CpuTimes>
+	{
+		let mut since = PerLogicalCoreData::empty(&LogicalCores::from(self.0.logical_core_indices().collect::<BTreeSet<_>>()));
+		for (logical_core_identifier, cpu_times) in self.0.iter()
+		{
+			if let Some(previous_cpu_times) = previous.0.get(logical_core_identifier)
+			{
+				since.set(logical_core_identifier, cpu_times.since(previous_cpu_times))
+			}
+		}
+		since
+	}
+
+	/// Those of `allowed` in both this and `previous`, an earlier sample, in ascending order of busy fraction over the interval; ties are in ascending order of logical core identifier.
+	///
+	/// Useful to choose the least-loaded logical cores when placing new workers.
+	pub fn least_loaded(&self, previous: &Self, allowed: &LogicalCores) -> Vec<LogicalCoreIdentifier>
+	{
+		let since = self.since(previous);
+		let mut busy_fractions: Vec<(f64, LogicalCoreIdentifier)> = allowed.iter().filter_map(|logical_core_identifier| since.get(*logical_core_identifier).map(|cpu_times| (cpu_times.busy_fraction(), *logical_core_identifier))).collect();
+		busy_fractions.sort_by(|&(left_busy_fraction, left_logical_core_identifier), &(right_busy_fraction, right_logical_core_identifier)| left_busy_fraction.partial_cmp(&right_busy_fraction).unwrap_or(Ordering::Equal).then(left_logical_core_identifier.cmp(&right_logical_core_identifier)));
+		busy_fractions.into_iter().map(|(_busy_fraction, logical_core_identifier)| logical_core_identifier).collect()
+	}
+}
+
+#[cfg(test)]
+mod core_utilisation_tests
+{
+	use super::*;
+
+	/// A modern kernel, with all ten columns, and lines which are not per logical core.
+	const ProcStat: &str = "cpu  300 10 200 4000 50 5 15 20 0 0\ncpu0 100 0 100 2000 25 5 5 10 0 0\ncpu1 200 10 100 2000 25 0 10 10 0 0\nintr 12345 0 0\nctxt 6789\nbtime 1700000000\nprocesses 100\n";
+
+	/// Later than `ProcStat`, with cpu0 busy and cpu1 idle; cpu3 has come online.
+	const LaterProcStat: &str = "cpu  460 10 240 4100 50 5 25 30 0 0\ncpu0 250 0 130 2000 25 5 15 20 0 0\ncpu1 210 10 110 2100 25 0 10 10 0 0\ncpu3 0 0 0 100 0 0 0 0 0 0\n";
+
+	#[test]
+	fn parses_proc_stat()
+	{
+		let core_utilisation = CoreUtilisation::parse_proc_stat(ProcStat).unwrap();
+		assert_eq!(core_utilisation.logical_core_indices().collect::<Vec<_>>(), vec![0, 1]);
+		assert_eq!(core_utilisation.get(1), Some(&CpuTimes { user: 200, nice: 10, system: 100, idle: 2000, iowait: 25, irq: 0, softirq: 10, steal: 10, guest: 0, guest_nice: 0 }));
+	}
+
+	#[test]
+	fn parses_short_lines_from_older_kernels()
+	{
+		// Linux 2.6.0 to 2.6.10 lack `steal`, `guest` and `guest_nice`; Linux 2.6.11 to 2.6.23 lack `guest` and `guest_nice`.
+		let core_utilisation = CoreUtilisation::parse_proc_stat("cpu 2 0 2 20 0 0 0\ncpu0 1 0 1 10 0 0 0\ncpu1 1 0 1 10 0 0 0 3\n").unwrap();
+		assert_eq!(core_utilisation.get(0), Some(&CpuTimes { user: 1, nice: 0, system: 1, idle: 10, iowait: 0, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 }));
+		assert_eq!(core_utilisation.get(1).unwrap().steal, 3);
+	}
+
+	#[test]
+	fn rejects_proc_stat_without_logical_cores()
+	{
+		for proc_stat in &["", "cpu 1 2 3 4\nintr 1\n"]
+		{
+			assert_eq!(CoreUtilisation::parse_proc_stat(proc_stat).unwrap_err().kind(), io::ErrorKind::InvalidData);
+		}
+
+		assert_eq!(CoreUtilisation::parse_proc_stat("cpu0 1 x 3 4\n").unwrap_err().kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn computes_times_since_previous_sample()
+	{
+		let previous = CoreUtilisation::parse_proc_stat(ProcStat).unwrap();
+		let current = CoreUtilisation::parse_proc_stat(LaterProcStat).unwrap();
+
+		let since = current.since(&previous);
+		assert_eq!(since.logical_core_indices().collect::<Vec<_>>(), vec![0, 1]);
+		assert_eq!(since.get(0), Some(&CpuTimes { user: 150, nice: 0, system: 30, idle: 0, iowait: 0, irq: 0, softirq: 10, steal: 10, guest: 0, guest_nice: 0 }));
+		assert_eq!(since.get(0).unwrap().busy_fraction(), 1.0);
+		assert_eq!(since.get(1).unwrap().busy(), 20);
+
+		let allowed = LogicalCores::from((0 .. 4).collect::<BTreeSet<_>>());
+		assert_eq!(current.least_loaded(&previous, &allowed), vec![1, 0]);
+	}
+
+	#[test]
+	fn computes_nothing_for_samples_without_logical_cores_in_common()
+	{
+		let previous = CoreUtilisation::parse_proc_stat("cpu0 1 0 1 10\n").unwrap();
+		let current = CoreUtilisation::parse_proc_stat("cpu1 2 0 2 20\n").unwrap();
+
+		assert_eq!(current.since(&previous).logical_core_indices().count(), 0);
+		assert_eq!(current.least_loaded(&previous, &LogicalCores::from((0 .. 2).collect::<BTreeSet<_>>())), Vec::new());
+	}
+
+	#[test]
+	fn computes_times_since_previous_sample_for_sparse_logical_cores()
+	{
+		let previous = CoreUtilisation::parse_proc_stat("cpu0 1 0 1 10\ncpu2 1 0 1 10\n").unwrap();
+		let current = CoreUtilisation::parse_proc_stat("cpu0 1 0 1 20\ncpu2 6 0 1 15\n").unwrap();
+
+		let since = current.since(&previous);
+		assert_eq!(since.logical_core_indices().collect::<Vec<_>>(), vec![0, 2]);
+		assert_eq!(since.get(2).unwrap().busy(), 5);
+		assert_eq!(current.least_loaded(&previous, &LogicalCores::from((0 .. 4).collect::<BTreeSet<_>>())), vec![0, 2]);
+	}
+}
@@ -0,0 +1,98 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Cumulative times a logical core has spent in various states, in clock ticks (`USER_HZ`, almost always 100 per second), as found in a `cpuN` line of `/proc/stat`.
+///
+/// Use `since()` to find the times over an interval.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CpuTimes
+{
+	/// Time running user code at normal priority; includes `guest`.
+	pub user: u64,
+
+	/// Time running user code at low priority (positive nice); includes `guest_nice`.
+	pub nice: u64,
+
+	/// Time running kernel code, excluding interrupts.
+	pub system: u64,
+
+	/// Time idle.
+	pub idle: u64,
+
+	/// Time idle whilst waiting for input or output to complete; unreliable.
+	pub iowait: u64,
+
+	/// Time servicing hardware interrupts.
+	pub irq: u64,
+
+	/// Time servicing software interrupts.
+	pub softirq: u64,
+
+	/// Time stolen by the hypervisor to run other virtual machines.
+	pub steal: u64,
+
+	/// Time running a virtual machine guest at normal priority.
+	pub guest: u64,
+
+	/// Time running a virtual machine guest at low priority.
+	pub guest_nice: u64,
+}
+
+impl CpuTimes
+{
+	/// Total time; `guest` and `guest_nice` are not added as they are already included in `user` and `nice`.
+	#[inline(always)]
+	pub fn total(&self) -> u64
+	{
+		self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+	}
+
+	/// Time idle, including `iowait`.
+	#[inline(always)]
+	pub fn idle_including_iowait(&self) -> u64
+	{
+		self.idle + self.iowait
+	}
+
+	/// Time not idle, including `irq`, `softirq` and `steal`.
+	#[inline(always)]
+	pub fn busy(&self) -> u64
+	{
+		self.total() - self.idle_including_iowait()
+	}
+
+	/// Busy time as a fraction of total time, from `0.0` to `1.0`; `0.0` if there is no total time.
+	#[inline(always)]
+	pub fn busy_fraction(&self) -> f64
+	{
+		let total = self.total();
+		if unlikely!(total == 0)
+		{
+			0.0
+		}
+		else
+		{
+			(self.busy() as f64) / (total as f64)
+		}
+	}
+
+	/// The times since `previous`, which should have been obtained earlier for the same logical core.
+	#[inline(always)]
+	pub fn since(&self, previous: &Self) -> Self
+	{
+		Self
+		{
+			user: self.user.saturating_sub(previous.user),
+			nice: self.nice.saturating_sub(previous.nice),
+			system: self.system.saturating_sub(previous.system),
+			idle: self.idle.saturating_sub(previous.idle),
+			iowait: self.iowait.saturating_sub(previous.iowait),
+			irq: self.irq.saturating_sub(previous.irq),
+			softirq: self.softirq.saturating_sub(previous.softirq),
+			steal: self.steal.saturating_sub(previous.steal),
+			guest: self.guest.saturating_sub(previous.guest),
+			guest_nice: self.guest_nice.saturating_sub(previous.guest_nice),
+		}
+	}
+}
@@ -0,0 +1,17 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A topological domain that logical cores are grouped into, such as a NUMA node or a package (socket).
+///
+/// Used with `PerDomainData`.
+pub trait Domain
+{
+	/// Identifies an instance of this domain, eg a `NumaNodeIdentifier`.
+	type Identifier: Copy + Ord + Hash + Default + Debug;
+
+	/// Finds the domain of a logical core.
+	///
+	/// Returns `None` if it can not be determined (eg this is not a NUMA machine).
+	fn domain_for_logical_core(logical_core_identifier: LogicalCoreIdentifier) -> Option<Self::Identifier>;
+}
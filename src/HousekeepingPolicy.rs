@@ -0,0 +1,145 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Which logical cores to reserve for housekeeping (eg the kernel, interrupts, logging and management threads), so that they are not used for data-plane workloads.
+///
+/// Used by `LogicalCores::for_workloads()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HousekeepingPolicy
+{
+	/// Reserve no logical cores.
+	None,
+
+	/// Reserve the first physical core (in topological order) and all of its simultaneous multi-threading (SMT) siblings, eg logical core 0 and its hyper thread sibling.
+	FirstPhysicalCore,
+
+	/// Reserve the first physical core (in topological order) of each package and all of their simultaneous multi-threading (SMT) siblings.
+	FirstPhysicalCorePerPackage,
+
+	/// Reserve the first physical core (in topological order) of each NUMA node and all of their simultaneous multi-threading (SMT) siblings.
+	FirstPhysicalCorePerNumaNode,
+
+	/// Reserve these logical cores.
+	Explicit(LogicalCores),
+}
+
+impl Default for HousekeepingPolicy
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		HousekeepingPolicy::FirstPhysicalCore
+	}
+}
+
+impl HousekeepingPolicy
+{
+	/// The logical cores in `pool` this policy reserves for housekeeping.
+	#[inline(always)]
+	pub fn housekeeping_logical_cores(&self, pool: &LogicalCores, topology: &Topology) -> LogicalCores
+	{
+		use self::HousekeepingPolicy::*;
+
+		#[inline(always)]
+		fn first_physical_core_of_each(topology: &Topology, groups: BTreeMap<impl Ord, LogicalCores>) -> BTreeSet<LogicalCoreIdentifier>
+		{
+			let mut housekeeping = BTreeSet::new();
+			for (_key, logical_cores) in groups
+			{
+				if let Some((_logical_core_topology, smt_siblings)) = topology.physical_cores(&logical_cores).into_iter().next()
+				{
+					housekeeping.extend(smt_siblings.iter().cloned());
+				}
+			}
+			housekeeping
+		}
+
+		let housekeeping = match *self
+		{
+			None => BTreeSet::new(),
+
+			FirstPhysicalCore =>
+			{
+				let mut whole_pool = BTreeMap::new();
+				whole_pool.insert((), pool.clone());
+				first_physical_core_of_each(topology, whole_pool)
+			}
+
+			FirstPhysicalCorePerPackage => first_physical_core_of_each(topology, topology.packages(pool)),
+
+			FirstPhysicalCorePerNumaNode => first_physical_core_of_each(topology, topology.numa_nodes(pool)),
+
+			Explicit(ref logical_cores) => logical_cores.intersection(pool).cloned().collect(),
+		};
+		LogicalCores::from(housekeeping)
+	}
+}
+
+
+#[cfg(test)]
+mod housekeeping_policy_tests
+{
+	use super::*;
+
+	/// Two packages, with one NUMA node each, of two physical cores with two simultaneous multi-threading (SMT) siblings; as on Linux, the second SMT sibling of each physical core is numbered after all the first ones.
+	fn pool_and_topology() -> (LogicalCores, Topology)
+	{
+		let pool = logical_cores(&[0, 1, 2, 3, 4, 5, 6, 7]);
+		let topology = Topology::from(PerLogicalCoreData::new(&pool, |logical_core_identifier|
+		{
+			let physical_core = logical_core_identifier % 4;
+			let package = physical_core / 2;
+			LogicalCoreTopology
+			{
+				package,
+				numa_node: package as NumaNodeIdentifier,
+				last_level_cache: package * 2,
+				physical_core,
+			}
+		}));
+		(pool, topology)
+	}
+
+	fn housekeeping_logical_cores(housekeeping_policy: HousekeepingPolicy, pool: &[LogicalCoreIdentifier]) -> LogicalCores
+	{
+		let (_pool, topology) = pool_and_topology();
+		housekeeping_policy.housekeeping_logical_cores(&logical_cores(pool), &topology)
+	}
+
+	#[test]
+	fn none_reserves_nothing()
+	{
+		assert_eq!(housekeeping_logical_cores(HousekeepingPolicy::None, &[0, 1, 2, 3, 4, 5, 6, 7]), logical_cores(&[]));
+	}
+
+	#[test]
+	fn first_physical_core_reserves_smt_siblings()
+	{
+		assert_eq!(HousekeepingPolicy::default(), HousekeepingPolicy::FirstPhysicalCore);
+		assert_eq!(housekeeping_logical_cores(HousekeepingPolicy::FirstPhysicalCore, &[0, 1, 2, 3, 4, 5, 6, 7]), logical_cores(&[0, 4]));
+		assert_eq!(housekeeping_logical_cores(HousekeepingPolicy::FirstPhysicalCore, &[1, 2, 3, 5, 6, 7]), logical_cores(&[1, 5]));
+	}
+
+	#[test]
+	fn first_physical_core_per_package_and_numa_node()
+	{
+		assert_eq!(housekeeping_logical_cores(HousekeepingPolicy::FirstPhysicalCorePerPackage, &[0, 1, 2, 3, 4, 5, 6, 7]), logical_cores(&[0, 2, 4, 6]));
+		assert_eq!(housekeeping_logical_cores(HousekeepingPolicy::FirstPhysicalCorePerNumaNode, &[1, 3, 5, 6, 7]), logical_cores(&[1, 5, 6]));
+	}
+
+	#[test]
+	fn explicit_is_restricted_to_pool()
+	{
+		assert_eq!(housekeeping_logical_cores(HousekeepingPolicy::Explicit(logical_cores(&[1, 9])), &[0, 1, 2]), logical_cores(&[1]));
+	}
+
+	#[test]
+	fn empty_pool_reserves_nothing()
+	{
+		for housekeeping_policy in [HousekeepingPolicy::FirstPhysicalCore, HousekeepingPolicy::FirstPhysicalCorePerPackage, HousekeepingPolicy::FirstPhysicalCorePerNumaNode]
+		{
+			assert_eq!(housekeeping_logical_cores(housekeeping_policy, &[]), logical_cores(&[]));
+		}
+	}
+}
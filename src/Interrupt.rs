@@ -0,0 +1,140 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A numbered interrupt, as listed in `/proc/interrupts`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interrupt
+{
+	/// Interrupt request (IRQ) number.
+	pub interrupt_request_number: InterruptRequestNumber,
+
+	/// How many times each online logical core has handled this interrupt.
+	pub counts: BTreeMap<LogicalCoreIdentifier, u64>,
+
+	/// The interrupt controller, hardware interrupt number, trigger type and devices (actions), eg `IO-APIC 4-edge ttyS0`.
+	pub description: String,
+}
+
+impl Interrupt
+{
+	/// Parses the contents of `/proc/interrupts`.
+	///
+	/// Architecture-specific interrupts which are not numbered, such as `NMI` and `LOC`, are ignored.
+	pub fn parse_proc_interrupts(proc_interrupts: &str) -> io::Result<Vec<Self>>
+	{
+		let mut lines = proc_interrupts.lines();
+
+		let header = lines.next().ok_or_else(|| LogicalCores::invalid_data("Missing header".to_string()))?;
+		let mut logical_core_identifiers = Vec::new();
+		for column in header.split_whitespace()
+		{
+			if !column.starts_with("CPU")
+			{
+				return Err(LogicalCores::invalid_data(format!("Invalid column '{}'", column)))
+			}
+			logical_core_identifiers.push(LogicalCoreIdentifier::from_str(&column["CPU".len() .. ]).map_err(|_| LogicalCores::invalid_data(format!("Invalid column '{}'", column)))?);
+		}
+
+		let mut interrupts = Vec::new();
+		for line in lines
+		{
+			let mut fields = line.split_whitespace();
+
+			let interrupt_request_number = match fields.next()
+			{
+				None => continue,
+				Some(name) => match InterruptRequestNumber::from_str(name.trim_end_matches(':'))
+				{
+					Err(_) => continue,
+					Ok(interrupt_request_number) => interrupt_request_number,
+				},
+			};
+
+			let mut counts = BTreeMap::new();
+			for logical_core_identifier in logical_core_identifiers.iter()
+			{
+				let count = fields.next().ok_or_else(|| LogicalCores::invalid_data(format!("Missing counts for interrupt {}", interrupt_request_number)))?;
+				counts.insert(*logical_core_identifier, u64::from_str(count).map_err(|_| LogicalCores::invalid_data(format!("Invalid count '{}' for interrupt {}", count, interrupt_request_number)))?);
+			}
+
+			interrupts.push
+			(
+				Self
+				{
+					interrupt_request_number,
+					counts,
+					description: fields.collect::<Vec<_>>().join(" "),
+				}
+			);
+		}
+		Ok(interrupts)
+	}
+
+	/// The total number of times this interrupt has been handled.
+	#[inline(always)]
+	pub fn total(&self) -> u64
+	{
+		self.counts.values().sum()
+	}
+
+	/// Affinity of this interrupt.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn irq_affinity(&self) -> IrqAffinity
+	{
+		IrqAffinity(self.interrupt_request_number)
+	}
+}
+
+#[cfg(test)]
+mod interrupt_tests
+{
+	use super::*;
+
+	#[test]
+	fn parse_proc_interrupts_parses_numbered_interrupts()
+	{
+		let proc_interrupts = "           CPU0       CPU1       CPU3\n  0:         44          0          1   IO-APIC   2-edge      timer\n  9:          0          0          0   IO-APIC   9-fasteoi   acpi\n 24:     123456        789          0   PCI-MSI 524288-edge      eth0-TxRx-0\nNMI:          0          0          0   Non-maskable interrupts\nLOC:    1234567    2345678    3456789   Local timer interrupts\nERR:          0\n";
+
+		let interrupts = Interrupt::parse_proc_interrupts(proc_interrupts).unwrap();
+
+		assert_eq!(interrupts.len(), 3);
+		assert_eq!(interrupts[0].interrupt_request_number, 0);
+		assert_eq!(interrupts[0].counts, vec![(0, 44), (1, 0), (3, 1)].into_iter().collect());
+		assert_eq!(interrupts[0].description, "IO-APIC 2-edge timer");
+		assert_eq!(interrupts[2].interrupt_request_number, 24);
+		assert_eq!(interrupts[2].total(), 123456 + 789);
+		assert_eq!(interrupts[2].description, "PCI-MSI 524288-edge eth0-TxRx-0");
+	}
+
+	#[test]
+	fn parse_proc_interrupts_allows_no_description()
+	{
+		let interrupts = Interrupt::parse_proc_interrupts("CPU0\n 5: 7\n").unwrap();
+
+		assert_eq!(interrupts, vec![Interrupt { interrupt_request_number: 5, counts: vec![(0, 7)].into_iter().collect(), description: String::new() }]);
+	}
+
+	#[test]
+	fn parse_proc_interrupts_rejects_malformed()
+	{
+		for malformed in &["", "CPU0 GPU1\n", "CPUx\n", "CPU0 CPU1\n 3: 1\n", "CPU0\n 3: many timer\n"]
+		{
+			assert_eq!(Interrupt::parse_proc_interrupts(malformed).unwrap_err().kind(), io::ErrorKind::InvalidData, "{:?}", malformed);
+		}
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn parse_proc_interrupts_parses_this_machine()
+	{
+		let interrupts = IrqAffinity::interrupts().unwrap();
+
+		let online = LogicalCores::online().unwrap();
+		for interrupt in interrupts.iter()
+		{
+			assert!(interrupt.counts.keys().all(|logical_core_identifier| online.contains(logical_core_identifier)));
+		}
+	}
+}
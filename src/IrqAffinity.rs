@@ -0,0 +1,84 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Affinity of an interrupt, ie which logical cores may handle it, as controlled by `/proc/irq/<interrupt_request_number>`.
+///
+/// Pinning a thread to a logical core is of little use if the interrupts for its network card are handled by the same logical core.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IrqAffinity(pub InterruptRequestNumber);
+
+impl IrqAffinity
+{
+	/// Lists numbered interrupts and how many times each logical core has handled them.
+	///
+	/// Slow as it will parse the file `/proc/interrupts`.
+	#[inline(always)]
+	pub fn interrupts() -> io::Result<Vec<Interrupt>>
+	{
+		Interrupt::parse_proc_interrupts(&read_to_string("/proc/interrupts")?)
+	}
+
+	/// All interrupts which have an affinity, in ascending order.
+	///
+	/// Slow as it will read the directory `/proc/irq`.
+	pub fn all() -> io::Result<Vec<Self>>
+	{
+		let mut all = Vec::new();
+		for directory_entry in read_dir("/proc/irq")?
+		{
+			if let Some(interrupt_request_number) = directory_entry?.file_name().to_str().and_then(|file_name| InterruptRequestNumber::from_str(file_name).ok())
+			{
+				all.push(IrqAffinity(interrupt_request_number))
+			}
+		}
+		all.sort();
+		Ok(all)
+	}
+
+	/// The logical cores requested to handle this interrupt (`smp_affinity_list`).
+	#[inline(always)]
+	pub fn affinity(self) -> io::Result<LogicalCores>
+	{
+		LogicalCores::read_linux_list_file(self.file_path("smp_affinity_list"))
+	}
+
+	/// Requests that only `logical_cores` handle this interrupt (`smp_affinity_list`).
+	///
+	/// Requires root or `CAP_SYS_ADMIN`.
+	///
+	/// Fails if none of `logical_cores` are online (`EINVAL`) or if the interrupt's affinity is managed by the kernel or can not be changed (`EIO`).
+	#[inline(always)]
+	pub fn set_affinity(self, logical_cores: &LogicalCores) -> io::Result<()>
+	{
+		OpenOptions::new().write(true).open(self.file_path("smp_affinity_list"))?.write_all(logical_cores.to_linux_list_string().as_bytes())
+	}
+
+	/// The logical cores actually handling this interrupt (`effective_affinity_list`); the kernel may use only a subset of those requested.
+	///
+	/// Fails with `NotFound` if the kernel does not report effective affinity (prior to Linux 4.15 or on some architectures).
+	#[inline(always)]
+	pub fn effective_affinity(self) -> io::Result<LogicalCores>
+	{
+		LogicalCores::read_linux_list_file(self.file_path("effective_affinity_list"))
+	}
+
+	/// The effective affinity if reported by the kernel, otherwise the requested affinity.
+	#[inline(always)]
+	pub fn effective_or_requested_affinity(self) -> io::Result<LogicalCores>
+	{
+		match self.effective_affinity()
+		{
+			Err(ref error) if error.kind() == io::ErrorKind::NotFound => self.affinity(),
+			result => result,
+		}
+	}
+
+	#[inline(always)]
+	fn file_path(self, file_name: &str) -> PathBuf
+	{
+		let mut file_path = PathBuf::from(format!("/proc/irq/{}", self.0));
+		file_path.push(file_name);
+		file_path
+	}
+}
@@ -0,0 +1,131 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Checks that logical cores intended for latency-critical threads (eg those from `LogicalCores::isolated_for_process()`) are really isolated.
+///
+/// Kernel state is read once, at construction; create a new instance to see changes, eg after moving interrupts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IsolationValidator
+{
+	isolated: LogicalCores,
+	rcu_nocbs: LogicalCores,
+	interrupts: BTreeMap<InterruptRequestNumber, LogicalCores>,
+}
+
+impl IsolationValidator
+{
+	/// Reads `/sys/devices/system/cpu/isolated`, `/sys/devices/system/cpu/nohz_full`, `/proc/cmdline` and the affinity of every interrupt in `/proc/irq`.
+	///
+	/// For each interrupt, the effective affinity (`effective_affinity_list`) is used if the kernel supports it, otherwise the requested affinity (`smp_affinity_list`).
+	/// Interrupts which are freed while this happens are ignored.
+	pub fn new() -> io::Result<Self>
+	{
+		let mut isolated: BTreeSet<LogicalCoreIdentifier> = LogicalCores::isolated()?.into();
+		isolated.extend(LogicalCores::nohz_full()?.iter());
+
+		Ok
+		(
+			Self
+			{
+				isolated: LogicalCores::from(isolated),
+				rcu_nocbs: LogicalCores::rcu_nocbs()?,
+				interrupts: Self::interrupt_affinities(IrqAffinity::all()?)?,
+			}
+		)
+	}
+
+	/// Validates `logical_cores`, returning warnings in ascending order of logical core; if there are no warnings, the logical cores are isolated.
+	pub fn validate(&self, logical_cores: &LogicalCores) -> Vec<IsolationWarning>
+	{
+		use self::IsolationWarning::*;
+
+		let mut warnings = Vec::new();
+		for logical_core_identifier in logical_cores.iter()
+		{
+			let logical_core_identifier = *logical_core_identifier;
+
+			if !self.isolated.contains(&logical_core_identifier)
+			{
+				warnings.push(NotIsolated { logical_core_identifier });
+			}
+
+			if !self.rcu_nocbs.contains(&logical_core_identifier)
+			{
+				warnings.push(RcuCallbacksNotOffloaded { logical_core_identifier });
+			}
+
+			let interrupt_request_numbers: Vec<InterruptRequestNumber> = self.interrupts.iter().filter(|&(_, affinity)| affinity.contains(&logical_core_identifier)).map(|(interrupt_request_number, _)| *interrupt_request_number).collect();
+			if !interrupt_request_numbers.is_empty()
+			{
+				warnings.push(InterruptsTargeted { logical_core_identifier, interrupt_request_numbers });
+			}
+		}
+		warnings
+	}
+
+	#[inline(always)]
+	fn interrupt_affinities(irq_affinities: impl IntoIterator<Item=IrqAffinity>) -> io::Result<BTreeMap<InterruptRequestNumber, LogicalCores>>
+	{
+		let mut interrupts = BTreeMap::new();
+		for irq_affinity in irq_affinities
+		{
+			match irq_affinity.effective_or_requested_affinity()
+			{
+				Ok(affinity) =>
+				{
+					interrupts.insert(irq_affinity.0, affinity);
+				}
+				Err(ref error) if error.kind() == io::ErrorKind::NotFound => (),
+				Err(error) => return Err(error),
+			}
+		}
+		Ok(interrupts)
+	}
+}
+
+#[cfg(test)]
+mod isolation_validator_tests
+{
+	use super::*;
+	use self::IsolationWarning::*;
+
+	#[test]
+	fn validate_warns_in_order_of_logical_core()
+	{
+		let mut interrupts = BTreeMap::new();
+		interrupts.insert(9, logical_cores(&[0, 1, 2]));
+		interrupts.insert(24, logical_cores(&[2]));
+		interrupts.insert(25, logical_cores(&[0]));
+		let isolation_validator = IsolationValidator
+		{
+			isolated: logical_cores(&[1, 2, 3]),
+			rcu_nocbs: logical_cores(&[2, 3]),
+			interrupts,
+		};
+
+		assert_eq!(isolation_validator.validate(&logical_cores(&[0, 1, 2])), vec!
+		[
+			NotIsolated { logical_core_identifier: 0 },
+			RcuCallbacksNotOffloaded { logical_core_identifier: 0 },
+			InterruptsTargeted { logical_core_identifier: 0, interrupt_request_numbers: vec![9, 25] },
+			RcuCallbacksNotOffloaded { logical_core_identifier: 1 },
+			InterruptsTargeted { logical_core_identifier: 1, interrupt_request_numbers: vec![9] },
+			InterruptsTargeted { logical_core_identifier: 2, interrupt_request_numbers: vec![9, 24] },
+		]);
+		assert_eq!(isolation_validator.validate(&logical_cores(&[3])), Vec::new());
+	}
+
+	#[test]
+	fn interrupt_affinities_ignores_freed_interrupts()
+	{
+		let freed = IrqAffinity(InterruptRequestNumber::MAX);
+		let mut irq_affinities = IrqAffinity::all().unwrap();
+		irq_affinities.push(freed);
+
+		let interrupts = IsolationValidator::interrupt_affinities(irq_affinities).unwrap();
+
+		assert!(!interrupts.contains_key(&freed.0));
+		assert!(IsolationValidator::new().is_ok());
+	}
+}
@@ -0,0 +1,50 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A reason why a logical core intended for a latency-critical thread is not fully isolated.
+///
+/// Produced by `IsolationValidator`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IsolationWarning
+{
+	/// The logical core is neither isolated (`isolcpus`) nor adaptive-ticks (`nohz_full`), so the scheduler and scheduling-clock interrupt may run other work on it.
+	NotIsolated
+	{
+		/// Logical core.
+		logical_core_identifier: LogicalCoreIdentifier,
+	},
+
+	/// The logical core is neither in `rcu_nocbs` nor `nohz_full` (which implies `rcu_nocbs`), so RCU callbacks may run on it.
+	RcuCallbacksNotOffloaded
+	{
+		/// Logical core.
+		logical_core_identifier: LogicalCoreIdentifier,
+	},
+
+	/// Interrupts are handled by the logical core.
+	InterruptsTargeted
+	{
+		/// Logical core.
+		logical_core_identifier: LogicalCoreIdentifier,
+
+		/// The interrupts which target the logical core.
+		interrupt_request_numbers: Vec<InterruptRequestNumber>,
+	},
+}
+
+impl Display for IsolationWarning
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::IsolationWarning::*;
+
+		match *self
+		{
+			NotIsolated { logical_core_identifier } => write!(f, "Logical core {} is neither in isolcpus nor nohz_full", logical_core_identifier),
+			RcuCallbacksNotOffloaded { logical_core_identifier } => write!(f, "Logical core {} is neither in rcu_nocbs nor nohz_full so may run RCU callbacks", logical_core_identifier),
+			InterruptsTargeted { logical_core_identifier, ref interrupt_request_numbers } => write!(f, "Logical core {} is targeted by interrupts {:?}", logical_core_identifier, interrupt_request_numbers),
+		}
+	}
+}
@@ -0,0 +1,245 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A hwloc-style location expression, which specifies logical cores by topological position rather than by `LogicalCoreIdentifier`.
+///
+/// This makes configuration portable between machines whose logical cores are numbered differently.
+///
+/// An expression is one or more whitespace-separated locations; each location is one or more `.`-separated levels of the form `type:indices`, eg `package:0.core:2-5.pu:0` or `numa:1`.
+/// Each level selects objects within those selected by the previous level; indices are relative to that parent object, so `package:1.core:0` is the first physical core of the second package.
+/// See `LocationObjectType` and `LocationIndices` for what is supported.
+///
+/// Locations are combined in order; a location prefixed with `~` removes its logical cores from those so far, eg `core:all ~core:0`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocationExpression
+{
+	locations: Vec<(bool, Vec<LocationExpressionLevel>)>,
+}
+
+impl Display for LocationExpression
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		for (location_index, &(excluded, ref levels)) in self.locations.iter().enumerate()
+		{
+			if location_index != 0
+			{
+				write!(f, " ")?;
+			}
+			if excluded
+			{
+				write!(f, "~")?;
+			}
+			for (level_index, level) in levels.iter().enumerate()
+			{
+				if level_index != 0
+				{
+					write!(f, ".")?;
+				}
+				write!(f, "{}", level)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for LocationExpression
+{
+	type Err = LocationExpressionError;
+
+	#[inline(always)]
+	fn from_str(value: &str) -> Result<Self, Self::Err>
+	{
+		let mut locations = Vec::new();
+		for location in value.split_whitespace()
+		{
+			let (excluded, location) = match location.strip_prefix('~')
+			{
+				Some(location) => (true, location),
+				None => (false, location),
+			};
+
+			let levels = location.split('.').map(LocationExpressionLevel::from_str).collect::<Result<Vec<_>, _>>()?;
+			locations.push((excluded, levels));
+		}
+
+		if locations.is_empty()
+		{
+			Err(LocationExpressionError::Empty)
+		}
+		else
+		{
+			Ok(Self { locations })
+		}
+	}
+}
+
+impl LocationExpression
+{
+	/// Resolves this expression against the topology of `pool`, eg `LogicalCores::valid_logical_cores_for_the_current_process()`.
+	///
+	/// Only logical cores in `pool` are considered, so indices are relative to what is available.
+	#[inline(always)]
+	pub fn resolve(&self, pool: &LogicalCores, topology: &Topology) -> Result<LogicalCores, LocationExpressionError>
+	{
+		let mut resolved = BTreeSet::new();
+		for &(excluded, ref levels) in self.locations.iter()
+		{
+			let mut parents = vec![pool.clone()];
+			for level in levels.iter()
+			{
+				let mut children = Vec::new();
+				for parent in parents.iter()
+				{
+					let objects = level.object_type.objects_within(parent, topology);
+					children.extend(level.indices.select(level.object_type, &objects)?.cloned());
+				}
+				parents = children;
+			}
+
+			for logical_cores in parents
+			{
+				for logical_core_identifier in logical_cores.iter()
+				{
+					if excluded
+					{
+						resolved.remove(logical_core_identifier);
+					}
+					else
+					{
+						resolved.insert(*logical_core_identifier);
+					}
+				}
+			}
+		}
+		Ok(LogicalCores::from(resolved))
+	}
+}
+
+
+#[cfg(test)]
+mod location_expression_tests
+{
+	use super::*;
+	use self::LocationObjectType::*;
+
+	/// Two packages (and NUMA nodes), each of two physical cores with two simultaneous multi-threading (SMT) siblings; as on Linux, the second SMT sibling of each physical core is numbered after all the first ones.
+	fn pool_and_topology() -> (LogicalCores, Topology)
+	{
+		let pool = logical_cores(&[0, 1, 2, 3, 4, 5, 6, 7]);
+		let topology = Topology::from(PerLogicalCoreData::new(&pool, |logical_core_identifier|
+		{
+			let physical_core = logical_core_identifier % 4;
+			let package = physical_core / 2;
+			LogicalCoreTopology
+			{
+				package,
+				numa_node: package as NumaNodeIdentifier,
+				last_level_cache: package * 2,
+				physical_core,
+			}
+		}));
+		(pool, topology)
+	}
+
+	fn resolve(expression: &str) -> Result<LogicalCores, LocationExpressionError>
+	{
+		let (pool, topology) = pool_and_topology();
+		LocationExpression::from_str(expression)?.resolve(&pool, &topology)
+	}
+
+	#[test]
+	fn resolves_levels()
+	{
+		assert_eq!(resolve("package:1"), Ok(logical_cores(&[2, 3, 6, 7])));
+		assert_eq!(resolve("package:0.core:1"), Ok(logical_cores(&[1, 5])));
+		assert_eq!(resolve("core:all.pu:0"), Ok(logical_cores(&[0, 1, 2, 3])));
+		assert_eq!(resolve("numa:1.llc:0.core:0-1.pu:0"), Ok(logical_cores(&[2, 3])));
+		assert_eq!(resolve("machine:0"), Ok(logical_cores(&[0, 1, 2, 3, 4, 5, 6, 7])));
+	}
+
+	#[test]
+	fn resolves_even_and_odd()
+	{
+		assert_eq!(resolve("core:even"), Ok(logical_cores(&[0, 2, 4, 6])));
+		assert_eq!(resolve("core:odd.pu:1"), Ok(logical_cores(&[5, 7])));
+		assert_eq!(resolve("pu:ODD"), Ok(logical_cores(&[1, 3, 5, 7])));
+	}
+
+	#[test]
+	fn resolves_index_ranges()
+	{
+		assert_eq!(resolve("pu:2-4"), Ok(logical_cores(&[2, 3, 4])));
+		assert_eq!(resolve("core:3-3"), Ok(logical_cores(&[3, 7])));
+	}
+
+	#[test]
+	fn resolves_unions_and_exclusions_in_order()
+	{
+		assert_eq!(resolve("socket:0 node:1"), Ok(logical_cores(&[0, 1, 2, 3, 4, 5, 6, 7])));
+		assert_eq!(resolve("machine:0 ~core:0"), Ok(logical_cores(&[1, 2, 3, 5, 6, 7])));
+		assert_eq!(resolve("machine:0 ~package:1 pu:7"), Ok(logical_cores(&[0, 1, 4, 5, 7])));
+		assert_eq!(resolve("~pu:0 pu:0"), Ok(logical_cores(&[0])));
+		assert_eq!(resolve("pu:0 ~pu:0"), Ok(logical_cores(&[])));
+	}
+
+	#[test]
+	fn rejects_out_of_range_indices()
+	{
+		assert_eq!(resolve("package:2"), Err(LocationExpressionError::IndexOutOfRange { object_type: Package, index: 2, available: 2 }));
+		assert_eq!(resolve("package:1.core:0-2"), Err(LocationExpressionError::IndexOutOfRange { object_type: Core, index: 2, available: 2 }));
+		assert_eq!(resolve("core:0.pu:2"), Err(LocationExpressionError::IndexOutOfRange { object_type: ProcessingUnit, index: 2, available: 2 }));
+	}
+
+	#[test]
+	fn rejects_malformed_expressions()
+	{
+		assert_eq!(LocationExpression::from_str(""), Err(LocationExpressionError::Empty));
+		assert_eq!(LocationExpression::from_str(" \t "), Err(LocationExpressionError::Empty));
+		assert_eq!(LocationExpression::from_str("package"), Err(LocationExpressionError::InvalidLevel("package".to_string())));
+		assert_eq!(LocationExpression::from_str("core:0."), Err(LocationExpressionError::InvalidLevel("".to_string())));
+		assert_eq!(LocationExpression::from_str("Widget:0"), Err(LocationExpressionError::UnknownObjectType("widget".to_string())));
+		assert_eq!(LocationExpression::from_str("core:3-1"), Err(LocationExpressionError::InvalidIndices("3-1".to_string())));
+		assert_eq!(LocationExpression::from_str("core:x"), Err(LocationExpressionError::InvalidIndices("x".to_string())));
+		assert_eq!(LocationExpression::from_str("core:-1"), Err(LocationExpressionError::InvalidIndices("-1".to_string())));
+	}
+
+	#[test]
+	fn display_round_trips()
+	{
+		let location_expression = LocationExpression::from_str("Socket:ALL.core:0-1  ~PU:odd thread:2-2 numanode:even.llc:0").unwrap();
+
+		let displayed = location_expression.to_string();
+
+		assert_eq!(displayed, "package:all.core:0-1 ~pu:odd pu:2 numa:even.llc:0");
+		assert_eq!(LocationExpression::from_str(&displayed), Ok(location_expression));
+	}
+
+	#[test]
+	fn parses_object_types()
+	{
+		for &(name, object_type) in &[("machine", Machine), ("package", Package), ("socket", Package), ("numa", NumaNode), ("numanode", NumaNode), ("node", NumaNode), ("llc", LastLevelCache), ("core", Core), ("pu", ProcessingUnit), ("thread", ProcessingUnit)]
+		{
+			assert_eq!(LocationObjectType::from_str(name), Ok(object_type));
+			assert_eq!(LocationObjectType::from_str(&name.to_ascii_uppercase()), Ok(object_type));
+			assert_eq!(LocationObjectType::from_str(&object_type.to_string()), Ok(object_type));
+		}
+	}
+
+	#[test]
+	fn selects_indices()
+	{
+		let objects = (0 .. 5).map(LogicalCores::from).collect::<Vec<_>>();
+		let select = |indices: LocationIndices| indices.select(Core, &objects).map(|selected| selected.cloned().collect::<Vec<_>>());
+
+		assert_eq!(select(LocationIndices::All), Ok(objects.clone()));
+		assert_eq!(select(LocationIndices::Even), Ok(vec![objects[0].clone(), objects[2].clone(), objects[4].clone()]));
+		assert_eq!(select(LocationIndices::Odd), Ok(vec![objects[1].clone(), objects[3].clone()]));
+		assert_eq!(select(LocationIndices::Range { first: 1, last: 2 }), Ok(vec![objects[1].clone(), objects[2].clone()]));
+		assert_eq!(select(LocationIndices::Range { first: 4, last: 5 }), Err(LocationExpressionError::IndexOutOfRange { object_type: Core, index: 5, available: 5 }));
+		assert_eq!(LocationIndices::Odd.select(Core, &objects[.. 1]).unwrap().count(), 0);
+		assert_eq!(LocationIndices::Even.select(Core, &[]).unwrap().count(), 0);
+	}
+}
@@ -0,0 +1,55 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// An error parsing or resolving a `LocationExpression`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LocationExpressionError
+{
+	/// The expression contained no locations.
+	Empty,
+
+	/// A level was not of the form `type:indices`.
+	InvalidLevel(String),
+
+	/// An object type (eg `core`) is not one supported.
+	UnknownObjectType(String),
+
+	/// Indices were not `all`, `even`, `odd`, an index or a range of indices.
+	InvalidIndices(String),
+
+	/// An index referred to an object which does not exist in the topology.
+	IndexOutOfRange
+	{
+		/// Object type.
+		object_type: LocationObjectType,
+
+		/// Index.
+		index: usize,
+
+		/// The number of objects of `object_type` available.
+		available: usize,
+	},
+}
+
+impl Display for LocationExpressionError
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::LocationExpressionError::*;
+
+		match *self
+		{
+			Empty => write!(f, "Location expression is empty"),
+			InvalidLevel(ref level) => write!(f, "Level '{}' is not of the form 'type:indices'", level),
+			UnknownObjectType(ref name) => write!(f, "Unknown object type '{}'", name),
+			InvalidIndices(ref indices) => write!(f, "Invalid indices '{}'", indices),
+			IndexOutOfRange { object_type, index, available } => write!(f, "There is no {} with index {} as only {} are available", object_type, index, available),
+		}
+	}
+}
+
+impl error::Error for LocationExpressionError
+{
+}
@@ -0,0 +1,49 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A level of a `LocationExpression`, such as `core:2-5`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocationExpressionLevel
+{
+	/// Object type.
+	pub object_type: LocationObjectType,
+
+	/// Which objects of `object_type` to select within each object selected by the previous level.
+	pub indices: LocationIndices,
+}
+
+impl Display for LocationExpressionLevel
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "{}:{}", self.object_type, self.indices)
+	}
+}
+
+impl FromStr for LocationExpressionLevel
+{
+	type Err = LocationExpressionError;
+
+	#[inline(always)]
+	fn from_str(value: &str) -> Result<Self, Self::Err>
+	{
+		let mut parts = value.splitn(2, ':');
+		let object_type = parts.next().unwrap();
+		let indices = match parts.next()
+		{
+			None => return Err(LocationExpressionError::InvalidLevel(value.to_string())),
+			Some(indices) => indices,
+		};
+
+		Ok
+		(
+			Self
+			{
+				object_type: object_type.parse()?,
+				indices: indices.parse()?,
+			}
+		)
+	}
+}
@@ -0,0 +1,126 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Which objects of a particular type to select in a level of a `LocationExpression`.
+///
+/// Indices are logical, ie they are ordinals in topological order relative to the parent object, and are not `LogicalCoreIdentifier`s or any other operating system identifier.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LocationIndices
+{
+	/// All objects (`all`).
+	All,
+
+	/// Objects with an even index (`even`).
+	Even,
+
+	/// Objects with an odd index (`odd`).
+	Odd,
+
+	/// Objects with an index from `first` to `last` inclusive (`first-last`, or just `first` if they are the same).
+	Range
+	{
+		/// First index.
+		first: usize,
+
+		/// Last index (inclusive).
+		last: usize,
+	},
+}
+
+impl Display for LocationIndices
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::LocationIndices::*;
+
+		match *self
+		{
+			All => write!(f, "all"),
+			Even => write!(f, "even"),
+			Odd => write!(f, "odd"),
+			Range { first, last } => if first == last
+			{
+				write!(f, "{}", first)
+			}
+			else
+			{
+				write!(f, "{}-{}", first, last)
+			},
+		}
+	}
+}
+
+impl FromStr for LocationIndices
+{
+	type Err = LocationExpressionError;
+
+	/// Case insensitive.
+	#[inline(always)]
+	fn from_str(value: &str) -> Result<Self, Self::Err>
+	{
+		use self::LocationIndices::*;
+
+		#[inline(always)]
+		fn parse_index(value: &str, index: &str) -> Result<usize, LocationExpressionError>
+		{
+			index.parse::<usize>().map_err(|_| LocationExpressionError::InvalidIndices(value.to_string()))
+		}
+
+		let indices = match &value.to_ascii_lowercase()[..]
+		{
+			"all" => All,
+			"even" => Even,
+			"odd" => Odd,
+			_ =>
+			{
+				let mut bounds = value.splitn(2, '-');
+				let first = parse_index(value, bounds.next().unwrap())?;
+				let last = match bounds.next()
+				{
+					None => first,
+					Some(last) => parse_index(value, last)?,
+				};
+
+				if unlikely!(last < first)
+				{
+					return Err(LocationExpressionError::InvalidIndices(value.to_string()))
+				}
+
+				Range { first, last }
+			}
+		};
+		Ok(indices)
+	}
+}
+
+impl LocationIndices
+{
+	/// Selects from `objects`.
+	///
+	/// Fails if a `Range` refers to an object which does not exist.
+	#[inline(always)]
+	pub fn select<'a>(self, object_type: LocationObjectType, objects: &'a [LogicalCores]) -> Result<impl Iterator<Item=&'a LogicalCores> + 'a, LocationExpressionError>
+	{
+		use self::LocationIndices::*;
+
+		let available = objects.len();
+		let (start, end, step) = match self
+		{
+			All => (0, available, 1),
+			Even => (0, available, 2),
+			Odd => (1, available, 2),
+			Range { first, last } =>
+			{
+				if unlikely!(last >= available)
+				{
+					return Err(LocationExpressionError::IndexOutOfRange { object_type, index: last, available })
+				}
+				(first, last + 1, 1)
+			}
+		};
+
+		Ok(objects.get(start .. end).unwrap_or(&[]).iter().step_by(step))
+	}
+}
@@ -0,0 +1,108 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A type of topological object in a `LocationExpression`.
+///
+/// The names used are those of hwloc, with some common synonyms.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LocationObjectType
+{
+	/// The whole pool of logical cores (`machine`).
+	Machine,
+
+	/// A package, also known as a socket (`package` or `socket`).
+	Package,
+
+	/// A NUMA node (`numa`, `numanode` or `node`).
+	NumaNode,
+
+	/// Logical cores sharing a last level cache (`llc`).
+	LastLevelCache,
+
+	/// A physical core (`core`).
+	Core,
+
+	/// A logical core, also known as a hyper thread or processing unit (`pu` or `thread`).
+	ProcessingUnit,
+}
+
+impl Display for LocationObjectType
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::LocationObjectType::*;
+
+		let name = match *self
+		{
+			Machine => "machine",
+			Package => "package",
+			NumaNode => "numa",
+			LastLevelCache => "llc",
+			Core => "core",
+			ProcessingUnit => "pu",
+		};
+		write!(f, "{}", name)
+	}
+}
+
+impl FromStr for LocationObjectType
+{
+	type Err = LocationExpressionError;
+
+	/// Case insensitive.
+	#[inline(always)]
+	fn from_str(value: &str) -> Result<Self, Self::Err>
+	{
+		use self::LocationObjectType::*;
+
+		let name = value.to_ascii_lowercase();
+		let object_type = match &name[..]
+		{
+			"machine" => Machine,
+			"package" | "socket" => Package,
+			"numa" | "numanode" | "node" => NumaNode,
+			"llc" => LastLevelCache,
+			"core" => Core,
+			"pu" | "thread" => ProcessingUnit,
+			_ => return Err(LocationExpressionError::UnknownObjectType(name)),
+		};
+		Ok(object_type)
+	}
+}
+
+impl LocationObjectType
+{
+	/// The objects of this type within `parent`, in topological order.
+	///
+	/// Objects are never empty.
+	#[inline(always)]
+	pub fn objects_within(self, parent: &LogicalCores, topology: &Topology) -> Vec<LogicalCores>
+	{
+		use self::LocationObjectType::*;
+
+		#[inline(always)]
+		fn values<Key>(groups: BTreeMap<Key, LogicalCores>) -> Vec<LogicalCores>
+		{
+			groups.into_values().collect()
+		}
+
+		match self
+		{
+			Machine => if parent.is_empty()
+			{
+				vec![]
+			}
+			else
+			{
+				vec![parent.clone()]
+			},
+			Package => values(topology.packages(parent)),
+			NumaNode => values(topology.numa_nodes(parent)),
+			LastLevelCache => values(topology.last_level_caches(parent)),
+			Core => values(topology.physical_cores(parent)),
+			ProcessingUnit => parent.iter().map(|logical_core_identifier| LogicalCores::from(*logical_core_identifier)).collect(),
+		}
+	}
+}
@@ -0,0 +1,92 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Frequency scaling (cpufreq) information for a logical core.
+///
+/// Everything is optional, as it depends on the cpufreq driver in use; virtual machines often have no cpufreq driver at all.
+/// Frequencies are in kilohertz.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LogicalCoreFrequency
+{
+	/// Current frequency as last requested by the governor or measured by the driver (`scaling_cur_freq`).
+	pub current_kilohertz: Option<u32>,
+
+	/// Minimum frequency supported by the hardware (`cpuinfo_min_freq`).
+	pub minimum_kilohertz: Option<u32>,
+
+	/// Maximum frequency supported by the hardware, which may include boost (`cpuinfo_max_freq`).
+	pub maximum_kilohertz: Option<u32>,
+
+	/// Minimum frequency the governor may choose (`scaling_min_freq`).
+	pub scaling_minimum_kilohertz: Option<u32>,
+
+	/// Maximum frequency the governor may choose (`scaling_max_freq`).
+	pub scaling_maximum_kilohertz: Option<u32>,
+
+	/// Governor, eg `performance`, `powersave` or `schedutil` (`scaling_governor`).
+	pub governor: Option<String>,
+
+	/// Driver, eg `intel_pstate`, `amd-pstate` or `acpi-cpufreq` (`scaling_driver`).
+	pub driver: Option<String>,
+
+	/// Whether frequency boost (eg Intel Turbo Boost or AMD Core Performance Boost) is enabled.
+	pub boost: Option<bool>,
+}
+
+impl LogicalCoreFrequency
+{
+	/// Discovers the frequency scaling information of a logical core.
+	///
+	/// Boost is read from the logical core's `cpufreq/boost` if present, otherwise from the global `/sys/devices/system/cpu/cpufreq/boost` or `/sys/devices/system/cpu/intel_pstate/no_turbo`.
+	///
+	/// Slow as it will read several files in `/sys/devices/system/cpu/cpu<logical_core_identifier>/cpufreq`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn discover(logical_core_identifier: LogicalCoreIdentifier) -> Self
+	{
+		let sys_path = SysPath::default();
+		let folder_path = sys_path.hyper_threads_path(&format!("cpu{}/cpufreq", logical_core_identifier));
+
+		#[inline(always)]
+		fn read_string(file_path: impl AsRef<Path>) -> Option<String>
+		{
+			read_to_string(file_path).ok().map(|value| value.trim().to_string())
+		}
+
+		#[inline(always)]
+		fn read_value<V: FromStr>(file_path: impl AsRef<Path>) -> Option<V>
+		{
+			read_string(file_path).and_then(|value| V::from_str(&value).ok())
+		}
+
+		let boost = read_value::<u8>(folder_path.join("boost")).or_else(|| read_value::<u8>(sys_path.hyper_threads_path("cpufreq/boost"))).map(|boost| boost != 0).or_else(|| read_value::<u8>(sys_path.hyper_threads_path("intel_pstate/no_turbo")).map(|no_turbo| no_turbo == 0));
+
+		Self
+		{
+			current_kilohertz: read_value(folder_path.join("scaling_cur_freq")),
+			minimum_kilohertz: read_value(folder_path.join("cpuinfo_min_freq")),
+			maximum_kilohertz: read_value(folder_path.join("cpuinfo_max_freq")),
+			scaling_minimum_kilohertz: read_value(folder_path.join("scaling_min_freq")),
+			scaling_maximum_kilohertz: read_value(folder_path.join("scaling_max_freq")),
+			governor: read_string(folder_path.join("scaling_governor")),
+			driver: read_string(folder_path.join("scaling_driver")),
+			boost,
+		}
+	}
+
+	/// Is the governor `powersave`?
+	///
+	/// With most drivers this keeps the logical core at its lowest frequency, which is rarely wanted for a pinned data-plane thread; with `intel_pstate` and `amd-pstate` in active mode it is the default and less harmful, but `performance` is still preferable.
+	#[inline(always)]
+	pub fn is_powersave(&self) -> bool
+	{
+		self.governor.as_ref().map(|governor| governor == "powersave").unwrap_or(false)
+	}
+
+	/// Is the governor `performance`?
+	#[inline(always)]
+	pub fn is_performance(&self) -> bool
+	{
+		self.governor.as_ref().map(|governor| governor == "performance").unwrap_or(false)
+	}
+}
@@ -0,0 +1,31 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A change in the logical cores which are online, as found by a `LogicalCoreHotplugWatcher`.
+///
+/// When a logical core goes offline Linux silently moves threads pinned to it elsewhere, so placements should be re-planned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogicalCoreHotplugEvent
+{
+	/// These logical cores have come online.
+	Online(LogicalCores),
+
+	/// These logical cores have gone offline.
+	Offline(LogicalCores),
+}
+
+impl Display for LogicalCoreHotplugEvent
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::LogicalCoreHotplugEvent::*;
+
+		match *self
+		{
+			Online(ref logical_cores) => write!(f, "Logical cores '{}' came online", logical_cores.to_linux_list_string()),
+			Offline(ref logical_cores) => write!(f, "Logical cores '{}' went offline", logical_cores.to_linux_list_string()),
+		}
+	}
+}
@@ -0,0 +1,228 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Watches for logical cores coming online and going offline.
+///
+/// Kernel uevents received on a netlink socket are used to wake up promptly; if such a socket can not be opened (eg because of a seccomp filter or network namespace), `/sys/devices/system/cpu/online` is polled instead.
+/// Either way, changes are found by comparing `/sys/devices/system/cpu/online` with that last seen, so no change is missed even if uevents are lost.
+#[derive(Debug)]
+pub struct LogicalCoreHotplugWatcher
+{
+	uevent_socket_file_descriptor: Option<RawFd>,
+	polling_interval: Duration,
+	online: LogicalCores,
+}
+
+impl Drop for LogicalCoreHotplugWatcher
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		if let Some(uevent_socket_file_descriptor) = self.uevent_socket_file_descriptor
+		{
+			unsafe { close(uevent_socket_file_descriptor) };
+		}
+	}
+}
+
+impl LogicalCoreHotplugWatcher
+{
+	/// Kernel uevent multicast group.
+	const KernelUeventGroup: u32 = 1;
+
+	/// Shorter polling intervals would truncate to a `poll()` timeout of zero and so busy-spin.
+	const MinimumPollingInterval: Duration = Duration::from_millis(1);
+
+	/// Creates a new instance, noting the logical cores which are currently online.
+	///
+	/// `polling_interval` is how often to check `/sys/devices/system/cpu/online` when waiting; it is also used as a timeout when waiting for uevents, as a safety net.
+	/// It is rounded up to at least 1 millisecond.
+	pub fn new(polling_interval: Duration) -> io::Result<Self>
+	{
+		Ok
+		(
+			Self
+			{
+				uevent_socket_file_descriptor: Self::open_uevent_socket().ok(),
+				polling_interval: max(polling_interval, Self::MinimumPollingInterval),
+				online: LogicalCores::online()?,
+			}
+		)
+	}
+
+	/// Are kernel uevents being used, or is `/sys/devices/system/cpu/online` being polled?
+	#[inline(always)]
+	pub fn is_using_uevents(&self) -> bool
+	{
+		self.uevent_socket_file_descriptor.is_some()
+	}
+
+	/// The logical cores last seen to be online.
+	#[inline(always)]
+	pub fn online(&self) -> &LogicalCores
+	{
+		&self.online
+	}
+
+	/// Blocks until logical cores come online or go offline.
+	///
+	/// Returned events are never empty; `Offline` events precede `Online` events.
+	pub fn wait(&mut self) -> io::Result<Vec<LogicalCoreHotplugEvent>>
+	{
+		loop
+		{
+			match self.uevent_socket_file_descriptor
+			{
+				None => sleep(self.polling_interval),
+				Some(uevent_socket_file_descriptor) => self.wait_for_uevents(uevent_socket_file_descriptor)?,
+			}
+
+			let events = self.check()?;
+			if !events.is_empty()
+			{
+				return Ok(events)
+			}
+		}
+	}
+
+	/// Checks, without blocking, whether logical cores have come online or gone offline since last checked.
+	///
+	/// `Offline` events precede `Online` events; returns an empty vector if there has been no change.
+	pub fn check(&mut self) -> io::Result<Vec<LogicalCoreHotplugEvent>>
+	{
+		let online = LogicalCores::online()?;
+		let events = Self::events(&self.online, &online);
+		self.online = online;
+		Ok(events)
+	}
+
+	/// Events for the change from `previously_online` to `online`; `Offline` events precede `Online` events.
+	fn events(previously_online: &LogicalCores, online: &LogicalCores) -> Vec<LogicalCoreHotplugEvent>
+	{
+		use self::LogicalCoreHotplugEvent::*;
+
+		let mut events = Vec::with_capacity(2);
+
+		let went_offline: BTreeSet<LogicalCoreIdentifier> = previously_online.difference(online).cloned().collect();
+		if !went_offline.is_empty()
+		{
+			events.push(Offline(LogicalCores::from(went_offline)));
+		}
+
+		let came_online: BTreeSet<LogicalCoreIdentifier> = online.difference(previously_online).cloned().collect();
+		if !came_online.is_empty()
+		{
+			events.push(Online(LogicalCores::from(came_online)));
+		}
+
+		events
+	}
+
+	/// Waits for at most `polling_interval` for uevents, then discards any received; they are only used to wake up.
+	fn wait_for_uevents(&self, uevent_socket_file_descriptor: RawFd) -> io::Result<()>
+	{
+		let mut poll_file_descriptor = pollfd
+		{
+			fd: uevent_socket_file_descriptor,
+			events: POLLIN,
+			revents: 0,
+		};
+		let timeout_in_milliseconds = min(self.polling_interval.as_nanos().div_ceil(1_000_000), c_int::MAX as u128) as c_int;
+		let result = unsafe { poll(&mut poll_file_descriptor, 1, timeout_in_milliseconds) };
+		if unlikely!(result == -1)
+		{
+			let error = LogicalCores::last_os_error();
+			return if error.raw_os_error() == Some(EINTR)
+			{
+				Ok(())
+			}
+			else
+			{
+				Err(error)
+			}
+		}
+
+		let mut buffer = [0u8; 8192];
+		loop
+		{
+			let result = unsafe { recv(uevent_socket_file_descriptor, buffer.as_mut_ptr() as *mut c_void, buffer.len(), MSG_DONTWAIT) };
+			if result == -1
+			{
+				let error = LogicalCores::last_os_error();
+				match error.raw_os_error()
+				{
+					// `ENOBUFS` means uevents were lost, which does not matter as `/sys/devices/system/cpu/online` is always checked.
+					Some(EINTR) | Some(ENOBUFS) => continue,
+					Some(EAGAIN) => return Ok(()),
+					_ => return Err(error),
+				}
+			}
+		}
+	}
+
+	fn open_uevent_socket() -> io::Result<RawFd>
+	{
+		let uevent_socket_file_descriptor = unsafe { socket(AF_NETLINK, SOCK_DGRAM | SOCK_CLOEXEC, NETLINK_KOBJECT_UEVENT) };
+		if unlikely!(uevent_socket_file_descriptor == -1)
+		{
+			return Err(LogicalCores::last_os_error())
+		}
+
+		let mut address: sockaddr_nl = unsafe { zeroed() };
+		address.nl_family = AF_NETLINK as sa_family_t;
+		address.nl_groups = Self::KernelUeventGroup;
+
+		let result = unsafe { bind(uevent_socket_file_descriptor, &address as *const sockaddr_nl as *const sockaddr, size_of::<sockaddr_nl>() as socklen_t) };
+		if likely!(result == 0)
+		{
+			Ok(uevent_socket_file_descriptor)
+		}
+		else
+		{
+			let error = LogicalCores::last_os_error();
+			unsafe { close(uevent_socket_file_descriptor) };
+			Err(error)
+		}
+	}
+}
+
+#[cfg(test)]
+mod logical_core_hotplug_watcher_tests
+{
+	use super::*;
+
+	#[test]
+	fn no_events_if_unchanged()
+	{
+		assert_eq!(LogicalCoreHotplugWatcher::events(&logical_cores(&[0, 1, 2]), &logical_cores(&[0, 1, 2])), Vec::new());
+	}
+
+	#[test]
+	fn went_offline()
+	{
+		assert_eq!(LogicalCoreHotplugWatcher::events(&logical_cores(&[0, 1, 2, 3]), &logical_cores(&[0, 2])), vec![LogicalCoreHotplugEvent::Offline(logical_cores(&[1, 3]))]);
+	}
+
+	#[test]
+	fn came_online()
+	{
+		assert_eq!(LogicalCoreHotplugWatcher::events(&logical_cores(&[0]), &logical_cores(&[0, 4, 5])), vec![LogicalCoreHotplugEvent::Online(logical_cores(&[4, 5]))]);
+	}
+
+	#[test]
+	fn offline_events_precede_online_events()
+	{
+		assert_eq!(LogicalCoreHotplugWatcher::events(&logical_cores(&[0, 1]), &logical_cores(&[0, 2])), vec![LogicalCoreHotplugEvent::Offline(logical_cores(&[1])), LogicalCoreHotplugEvent::Online(logical_cores(&[2]))]);
+	}
+
+	#[test]
+	fn check_finds_no_change()
+	{
+		let mut logical_core_hotplug_watcher = LogicalCoreHotplugWatcher::new(Duration::from_nanos(1)).unwrap();
+
+		assert_eq!(logical_core_hotplug_watcher.polling_interval, LogicalCoreHotplugWatcher::MinimumPollingInterval);
+		assert_eq!(logical_core_hotplug_watcher.online(), &LogicalCores::online().unwrap());
+		assert_eq!(logical_core_hotplug_watcher.check().unwrap(), Vec::new());
+	}
+}
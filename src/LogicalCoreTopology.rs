@@ -0,0 +1,105 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Where a logical core is in the topology of the machine.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LogicalCoreTopology
+{
+	/// Package (socket).
+	pub package: PackageIdentifier,
+
+	/// NUMA node.
+	pub numa_node: NumaNodeIdentifier,
+
+	/// Last level cache (eg the level 3 cache), identified by the lowest logical core which shares it.
+	pub last_level_cache: LogicalCoreIdentifier,
+
+	/// Physical core within `package`.
+	///
+	/// Logical cores with the same `package` and `physical_core` are simultaneous multi-threading (SMT) siblings (hyper threads).
+	pub physical_core: PhysicalCoreIdentifier,
+}
+
+impl LogicalCoreTopology
+{
+	/// Used for a logical core whose topology is unknown; it is assumed to be the only logical core of a physical core with its own last level cache in package 0 and NUMA node 0.
+	#[inline(always)]
+	pub fn unknown(logical_core_identifier: LogicalCoreIdentifier) -> Self
+	{
+		Self
+		{
+			package: 0,
+			numa_node: 0,
+			last_level_cache: logical_core_identifier,
+			physical_core: logical_core_identifier,
+		}
+	}
+
+	/// Discovers the topology of a logical core.
+	///
+	/// Anything which can not be determined is as for `unknown()`.
+	///
+	/// Slow as it will read several files in `/sys/devices/system/cpu/cpu<logical_core_identifier>`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn discover(logical_core_identifier: LogicalCoreIdentifier) -> Self
+	{
+		let sys_path = SysPath::default();
+		let hyper_thread = HyperThread::from(logical_core_identifier);
+
+		Self
+		{
+			package: hyper_thread.underlying_hardware_physical_socket_identifier(&sys_path).unwrap_or(0),
+			numa_node: LogicalCores::numa_node_for_logical_core(logical_core_identifier).unwrap_or(0),
+			last_level_cache: Self::discover_last_level_cache(&sys_path, hyper_thread).unwrap_or(logical_core_identifier),
+			physical_core: hyper_thread.underlying_hardware_physical_core_identifier(&sys_path).unwrap_or(logical_core_identifier),
+		}
+	}
+
+	/// The last level cache is the one with the highest `level` in `/sys/devices/system/cpu/cpu<N>/cache/index<M>`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	fn discover_last_level_cache(sys_path: &SysPath, hyper_thread: HyperThread) -> Option<LogicalCoreIdentifier>
+	{
+		let directory_entries = read_dir(sys_path.hyper_thread_path(hyper_thread, "cache")).ok()?;
+
+		let mut last_level_cache: Option<(u8, LogicalCoreIdentifier)> = None;
+		for directory_entry in directory_entries
+		{
+			let folder_path = match directory_entry
+			{
+				Err(_) => continue,
+				Ok(directory_entry) => directory_entry.path(),
+			};
+
+			let level = match read_to_string(folder_path.join("level"))
+			{
+				Err(_) => continue,
+				Ok(level) => match u8::from_str(level.trim())
+				{
+					Err(_) => continue,
+					Ok(level) => level,
+				},
+			};
+
+			if let Some((highest_level, _)) = last_level_cache
+			{
+				if level <= highest_level
+				{
+					continue
+				}
+			}
+
+			if let Ok(shared_with) = LogicalCores::read_linux_list_file(folder_path.join("shared_cpu_list"))
+			{
+				if let Some(lowest_logical_core_identifier) = shared_with.iter().next()
+				{
+					last_level_cache = Some((level, *lowest_logical_core_identifier))
+				}
+			}
+		}
+
+		last_level_cache.map(|(_, lowest_logical_core_identifier)| lowest_logical_core_identifier)
+	}
+}
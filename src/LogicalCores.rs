@@ -72,12 +72,12 @@ impl From<BTreeSet<HyperThread>> for LogicalCores
 	}
 }
 
-impl Into<BTreeSet<LogicalCoreIdentifier>> for LogicalCores
+impl From<LogicalCores> for BTreeSet<LogicalCoreIdentifier>
 {
 	#[inline(always)]
-	fn into(self) -> BTreeSet<LogicalCoreIdentifier>
+	fn from(logical_cores: LogicalCores) -> Self
 	{
-		self.0
+		logical_cores.0
 	}
 }
 
@@ -152,6 +152,139 @@ impl LogicalCores
 		Self::from(HyperThread::valid_hyper_threads_for_the_current_process(&ProcPath::default()))
 	}
 
+	/// Logical cores which are online, including those not available to the current process.
+	///
+	/// Slow as it will parse the file `/sys/devices/system/cpu/online`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn online() -> io::Result<Self>
+	{
+		Self::read_linux_list_file(SysPath::default().hyper_threads_path("online"))
+	}
+
+	/// Logical cores for the current process which are suitable for data-plane workloads.
+	///
+	/// Starts with `valid_logical_cores_for_the_current_process()` and removes:-
+	///
+	/// * isolated logical cores (`/sys/devices/system/cpu/isolated`, set by the kernel command line `isolcpus`);
+	/// * adaptive-ticks (tickless) logical cores (`/sys/devices/system/cpu/nohz_full`, set by the kernel command line `nohz_full`);
+	/// * logical cores reserved for housekeeping by `housekeeping_policy`.
+	///
+	/// ***Only valid at start up before `sched_setaffinity()` has been called.***
+	///
+	/// Fails if the files in `/sys/devices/system/cpu` can not be read or if no logical cores remain.
+	///
+	/// Slow as it will parse `/proc/self/status` and discover the topology of the current process' logical cores.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn for_workloads(housekeeping_policy: &HousekeepingPolicy) -> io::Result<Self>
+	{
+		let pool = Self::valid_logical_cores_for_the_current_process();
+		let housekeeping = housekeeping_policy.housekeeping_logical_cores(&pool, &Topology::discover(&pool));
+		let isolated = Self::isolated()?;
+		let nohz_full = Self::nohz_full()?;
+
+		let workloads: BTreeSet<LogicalCoreIdentifier> = pool.iter().filter(|logical_core_identifier| !isolated.contains(logical_core_identifier) && !nohz_full.contains(logical_core_identifier) && !housekeeping.contains(logical_core_identifier)).cloned().collect();
+		if unlikely!(workloads.is_empty())
+		{
+			return Err(io::Error::other("No logical cores remain for workloads"))
+		}
+		Ok(Self(workloads))
+	}
+
+	/// Logical cores isolated from the general scheduler by the kernel command line `isolcpus`.
+	///
+	/// Empty if there are none.
+	///
+	/// Slow as it will parse the file `/sys/devices/system/cpu/isolated`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn isolated() -> io::Result<Self>
+	{
+		Self::read_optional_linux_list_file(SysPath::default().hyper_threads_path("isolated"))
+	}
+
+	/// Logical cores which are adaptive-ticks (tickless) because of the kernel command line `nohz_full`.
+	///
+	/// Empty if there are none or the kernel does not support adaptive-ticks.
+	///
+	/// Slow as it will parse the file `/sys/devices/system/cpu/nohz_full`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn nohz_full() -> io::Result<Self>
+	{
+		match read_to_string(SysPath::default().hyper_threads_path("nohz_full"))
+		{
+			Err(ref error) if error.kind() == io::ErrorKind::NotFound => Ok(Self(BTreeSet::new())),
+			Err(error) => Err(error),
+			Ok(nohz_full) => Self::from_nohz_full_string(&nohz_full),
+		}
+	}
+
+	/// As `from_linux_list_string()`, but `(null)`, which kernels supporting adaptive-ticks report if booted without `nohz_full`, is an empty set of logical cores.
+	#[inline(always)]
+	fn from_nohz_full_string(nohz_full: &str) -> io::Result<Self>
+	{
+		if nohz_full.trim() == "(null)"
+		{
+			Ok(Self(BTreeSet::new()))
+		}
+		else
+		{
+			Self::from_linux_list_string(nohz_full)
+		}
+	}
+
+	/// Logical cores for the current process which are isolated (`isolcpus`) or adaptive-ticks (`nohz_full`), and so suitable for latency-critical threads.
+	///
+	/// The current process is only able to use such logical cores if it was started with an appropriate affinity, eg by `taskset`.
+	/// Use `IsolationValidator` to check that RCU callbacks and interrupts have also been moved away from them.
+	///
+	/// ***Only valid at start up before `sched_setaffinity()` has been called.***
+	///
+	/// Slow as it will parse `/proc/self/status`, `/sys/devices/system/cpu/isolated` and `/sys/devices/system/cpu/nohz_full`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn isolated_for_process() -> io::Result<Self>
+	{
+		let pool = Self::valid_logical_cores_for_the_current_process();
+		let isolated = Self::isolated()?;
+		let nohz_full = Self::nohz_full()?;
+
+		Ok(Self(pool.iter().filter(|logical_core_identifier| isolated.contains(logical_core_identifier) || nohz_full.contains(logical_core_identifier)).cloned().collect()))
+	}
+
+	/// Logical cores whose RCU callbacks are offloaded to kernel threads, either explicitly by the kernel command line `rcu_nocbs` or implicitly by being `nohz_full()`.
+	///
+	/// Empty if there are none; `rcu_nocbs=all` is all possible logical cores.
+	///
+	/// Slow as it will parse the files `/proc/cmdline` and `/sys/devices/system/cpu/nohz_full`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn rcu_nocbs() -> io::Result<Self>
+	{
+		let command_line = read_to_string("/proc/cmdline")?;
+
+		let mut rcu_nocbs: BTreeSet<LogicalCoreIdentifier> = match Self::rcu_nocbs_parameter(&command_line)
+		{
+			None => BTreeSet::new(),
+			Some("all") => Self::read_linux_list_file(SysPath::default().hyper_threads_path("possible"))?.into(),
+			Some(linux_list_string) => Self::from_linux_list_string(linux_list_string)?.into(),
+		};
+		rcu_nocbs.extend(Self::nohz_full()?.iter());
+		Ok(Self(rcu_nocbs))
+	}
+
+	/// The value of the last `rcu_nocbs=` on the kernel command line; anything after `--` is passed to init rather than being a kernel parameter.
+	#[inline(always)]
+	fn rcu_nocbs_parameter(command_line: &str) -> Option<&str>
+	{
+		let parameters: Vec<&str> = command_line.split_whitespace().collect();
+		let kernel_parameters = match parameters.iter().position(|&parameter| parameter == "--")
+		{
+			None => &parameters[..],
+			Some(separator_index) => &parameters[.. separator_index],
+		};
+		kernel_parameters.iter().filter_map(|parameter| parameter.strip_prefix("rcu_nocbs=")).next_back()
+	}
+
 	/// Creates an empty set of per logical core data.
 	#[inline(always)]
 	pub fn empty_per_logical_core_data<PerLogicalCore>(&self) -> PerLogicalCoreData<PerLogicalCore>
@@ -166,6 +299,15 @@ impl LogicalCores
 		PerLogicalCoreData::new(self, constructor)
 	}
 
+	/// Creates a populated set of per logical core data, with `constructor` being run on a thread pinned to each logical core.
+	///
+	/// See `PerLogicalCoreData::new_on_each_core()`.
+	#[inline(always)]
+	pub fn populate_per_logical_core_data_on_each_core<PerLogicalCore: Send>(&self, constructor: impl Fn(LogicalCoreIdentifier) -> PerLogicalCore + Sync) -> io::Result<PerLogicalCoreData<PerLogicalCore>>
+	{
+		PerLogicalCoreData::new_on_each_core(self, constructor)
+	}
+
 	/// Sets thread affinity to just the `logical_core_identifier`.
 	///
 	/// Not the same as `pthread_sched_getaffinity()`.
@@ -193,6 +335,211 @@ impl LogicalCores
 		HyperThread::current_hyper_thread().into()
 	}
 
+	/// NUMA nodes of these logical cores.
+	///
+	/// Logical cores whose NUMA node can not be determined are ignored; the result is empty if this is not a NUMA machine.
+	///
+	/// Slow as it will read a directory in `/sys/devices/system/cpu` for each logical core.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn numa_nodes(&self) -> NumaNodes
+	{
+		let mut numa_nodes = BTreeSet::new();
+		for logical_core_identifier in self.0.iter()
+		{
+			if let Some(numa_node) = Self::numa_node_for_logical_core(*logical_core_identifier)
+			{
+				numa_nodes.insert(numa_node);
+			}
+		}
+		NumaNodes::from(numa_nodes)
+	}
+
+	/// NUMA node of a logical core.
+	///
+	/// Returns `None` if this is not a NUMA machine or the logical core's NUMA node can not be determined.
+	///
+	/// Slow as it will read the directory `/sys/devices/system/cpu/cpu<logical_core_identifier>`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn numa_node_for_logical_core(logical_core_identifier: LogicalCoreIdentifier) -> Option<NumaNodeIdentifier>
+	{
+		let folder_path = SysPath::default().hyper_threads_path(&format!("cpu{}", logical_core_identifier));
+		let directory_entries = match read_dir(folder_path)
+		{
+			Err(_) => return None,
+			Ok(directory_entries) => directory_entries,
+		};
+
+		for directory_entry in directory_entries.flatten()
+		{
+			let file_name = directory_entry.file_name();
+			if let Some(numa_node) = file_name.to_str().and_then(|file_name| file_name.strip_prefix("node")).and_then(|numa_node| NumaNodeIdentifier::from_str(numa_node).ok())
+			{
+				return Some(numa_node)
+			}
+		}
+		None
+	}
+
+	/// Package (socket) of a logical core.
+	///
+	/// Returns `None` if the logical core's package can not be determined.
+	///
+	/// Slow as it will read the file `/sys/devices/system/cpu/cpu<logical_core_identifier>/topology/physical_package_id`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn package_for_logical_core(logical_core_identifier: LogicalCoreIdentifier) -> Option<PackageIdentifier>
+	{
+		HyperThread::from(logical_core_identifier).underlying_hardware_physical_socket_identifier(&SysPath::default()).ok()
+	}
+
+	/// The logical core which last processed incoming packets for a socket (`SO_INCOMING_CPU`).
+	///
+	/// This may be a logical core which is not available to the current process; see `PerLogicalCoreData::dispatch_to_owner()`.
+	///
+	/// Fails if the kernel does not support `SO_INCOMING_CPU` (prior to Linux 3.19), `socket_file_descriptor` is not a socket or no incoming packets have yet been processed (`NotFound`).
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn incoming_logical_core_for_socket(socket_file_descriptor: RawFd) -> io::Result<LogicalCoreIdentifier>
+	{
+		let mut value: c_int = 0;
+		let mut length = size_of::<c_int>() as socklen_t;
+		let result = unsafe { getsockopt(socket_file_descriptor, SOL_SOCKET, SO_INCOMING_CPU, &mut value as *mut c_int as *mut c_void, &mut length) };
+		if likely!(result == 0)
+		{
+			if unlikely!(value == -1)
+			{
+				return Err(io::Error::new(io::ErrorKind::NotFound, "No incoming packets have been processed for the socket"))
+			}
+			if unlikely!(value < 0 || value > (LogicalCoreIdentifier::MAX as c_int))
+			{
+				return Err(io::Error::new(io::ErrorKind::InvalidData, format!("SO_INCOMING_CPU was out of range ({})", value)))
+			}
+			Ok(value as LogicalCoreIdentifier)
+		}
+		else
+		{
+			Err(Self::last_os_error())
+		}
+	}
+
+	/// Sets the logical core which is expected to process incoming packets for a socket (`SO_INCOMING_CPU`).
+	///
+	/// On a listening socket in a `SO_REUSEPORT` group (since Linux 4.4), this makes the kernel prefer that socket for connections whose packets arrive on `logical_core_identifier`.
+	///
+	/// Fails if the kernel does not support setting `SO_INCOMING_CPU` or `socket_file_descriptor` is not a socket.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn set_incoming_logical_core_for_socket(socket_file_descriptor: RawFd, logical_core_identifier: LogicalCoreIdentifier) -> io::Result<()>
+	{
+		let value = logical_core_identifier as c_int;
+		let result = unsafe { setsockopt(socket_file_descriptor, SOL_SOCKET, SO_INCOMING_CPU, &value as *const c_int as *const c_void, size_of::<c_int>() as socklen_t) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(Self::last_os_error())
+		}
+	}
+
+	/// Capacity of a logical core relative to the most capable logical core in the system, from 1 to 1024 (`UtilizationClamp::Scale`), as used by the scheduler on heterogeneous systems.
+	///
+	/// Returns `None` if the kernel does not report capacity, which is usual for homogeneous systems.
+	///
+	/// Slow as it will read the file `/sys/devices/system/cpu/cpu<logical_core_identifier>/cpu_capacity`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn capacity_for_logical_core(logical_core_identifier: LogicalCoreIdentifier) -> Option<u16>
+	{
+		let file_path = SysPath::default().hyper_threads_path(&format!("cpu{}/cpu_capacity", logical_core_identifier));
+		match read_to_string(file_path)
+		{
+			Err(_) => None,
+			Ok(capacity) => u16::from_str(capacity.trim()).ok(),
+		}
+	}
+
+	/// Those of these logical cores which are performance cores (eg Intel P-cores or Arm big cores).
+	///
+	/// On a homogeneous system, this is all of them.
+	///
+	/// See `efficiency_cores()` for how performance cores are discovered.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn performance_cores(&self) -> io::Result<Self>
+	{
+		match Self::hybrid_core_types()?
+		{
+			None => Ok(self.clone()),
+			Some((performance_cores, _efficiency_cores)) => Ok(Self(self.0.intersection(&performance_cores.0).cloned().collect())),
+		}
+	}
+
+	/// Those of these logical cores which are efficiency cores (eg Intel E-cores or Arm LITTLE cores), which latency-sensitive threads should avoid.
+	///
+	/// On a homogeneous system, this is empty.
+	///
+	/// On Intel hybrid systems, performance and efficiency cores are those listed in `/sys/devices/cpu_core/cpus` and `/sys/devices/cpu_atom/cpus`.
+	/// Otherwise, if the capacities of online logical cores (see `capacity_for_logical_core()`) differ, the performance cores are those with the greatest capacity and the efficiency cores are the rest.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn efficiency_cores(&self) -> io::Result<Self>
+	{
+		match Self::hybrid_core_types()?
+		{
+			None => Ok(Self(BTreeSet::new())),
+			Some((_performance_cores, efficiency_cores)) => Ok(Self(self.0.intersection(&efficiency_cores.0).cloned().collect())),
+		}
+	}
+
+	/// Returns `None` for a homogeneous system, otherwise the performance and efficiency cores.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	fn hybrid_core_types() -> io::Result<Option<(Self, Self)>>
+	{
+		let performance_cores = Self::read_optional_linux_list_file("/sys/devices/cpu_core/cpus")?;
+		if !performance_cores.is_empty()
+		{
+			let efficiency_cores = Self::read_optional_linux_list_file("/sys/devices/cpu_atom/cpus")?;
+			return Ok(Some((performance_cores, efficiency_cores)))
+		}
+
+		let mut capacities = BTreeMap::new();
+		for &logical_core_identifier in Self::online()?.iter()
+		{
+			if let Some(capacity) = Self::capacity_for_logical_core(logical_core_identifier)
+			{
+				capacities.insert(logical_core_identifier, capacity);
+			}
+		}
+
+		Ok(Self::hybrid_core_types_by_capacity(capacities))
+	}
+
+	/// Returns `None` if all logical cores have the same capacity (or there are none), otherwise the performance cores (those with the greatest capacity) and the efficiency cores (the rest).
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	fn hybrid_core_types_by_capacity(capacities: BTreeMap<LogicalCoreIdentifier, u16>) -> Option<(Self, Self)>
+	{
+		let maximum_capacity = *capacities.values().max()?;
+		if capacities.values().all(|&capacity| capacity == maximum_capacity)
+		{
+			return None
+		}
+
+		let (performance_cores, efficiency_cores): (BTreeMap<_, _>, BTreeMap<_, _>) = capacities.into_iter().partition(|&(_, capacity)| capacity == maximum_capacity);
+		Some((Self(performance_cores.into_keys().collect()), Self(efficiency_cores.into_keys().collect())))
+	}
+
+	/// Frequency scaling (cpufreq) information for each of these logical cores, eg to check at start up that none use the `powersave` governor.
+	///
+	/// Slow as it will read several files in `/sys/devices/system/cpu` for each logical core.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn frequencies(&self) -> PerLogicalCoreData<LogicalCoreFrequency>
+	{
+		PerLogicalCoreData::new(self, LogicalCoreFrequency::discover)
+	}
+
 	/// Is setting process affinity is supported?
 	///
 	/// Note that on emscripten and fuschia an error (`ENOSYS`) by the platform will always be returned as of the 3rd December 2018.
@@ -245,6 +592,16 @@ impl LogicalCores
 		self._set_process_affinity(process_identifier)
 	}
 
+	/// Sets the logical core affinity of a thread identified by its Linux task identifier (`tid`).
+	///
+	/// Unlike `set_thread_affinity()`, the thread need not be in the current process; failure occurs as for `set_process_affinity()`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn set_task_affinity(&self, task_identifier: TaskIdentifier) -> io::Result<()>
+	{
+		self._set_process_affinity(task_identifier)
+	}
+
 	/// Sets the thread's logical core affinity.
 	///
 	/// Threads are never normally resident on just one core, and hence a lot of thread local opimizations (eg with clever non-blocking alogorithms) are useless.
@@ -296,12 +653,214 @@ impl LogicalCores
 		unsafe { ::kernel32::GetCurrentThread() }
 	}
 
+	/// Parses a Linux list string, such as `0-3,8,10-11`, as used in `/sys/devices/system/cpu/online` and on the Linux kernel command line.
+	///
+	/// Leading and trailing whitespace (such as a final line feed) is ignored; an empty string is an empty set of logical cores.
+	///
+	/// The rarely used range syntax `first-last:used/group_size` (eg `0-7:2/4`, which is the same as `0,1,4,5`) is supported.
+	///
+	/// Fails with `InvalidData` if the string is malformed.
+	pub fn from_linux_list_string(linux_list_string: &str) -> io::Result<Self>
+	{
+		#[inline(always)]
+		fn parse_index(index: &str) -> io::Result<LogicalCoreIdentifier>
+		{
+			LogicalCoreIdentifier::from_str(index).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid logical core index '{}'", index)))
+		}
+
+		let mut logical_cores = BTreeSet::new();
+
+		let linux_list_string = linux_list_string.trim();
+		if linux_list_string.is_empty()
+		{
+			return Ok(Self(logical_cores))
+		}
+
+		for index_or_range in linux_list_string.split(',')
+		{
+			let mut range_and_groups = index_or_range.splitn(2, ':');
+			let range = range_and_groups.next().unwrap();
+
+			let mut first_and_last = range.splitn(2, '-');
+			let first = parse_index(first_and_last.next().unwrap())?;
+			let last = match first_and_last.next()
+			{
+				None => first,
+				Some(last) => parse_index(last)?,
+			};
+			if unlikely!(first > last)
+			{
+				return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Range '{}' is descending", range)))
+			}
+
+			let (used, group_size) = match range_and_groups.next()
+			{
+				None => (1, 1),
+				Some(groups) =>
+				{
+					let mut used_and_group_size = groups.splitn(2, '/');
+					let used = parse_index(used_and_group_size.next().unwrap())?;
+					let group_size = match used_and_group_size.next()
+					{
+						None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Groups '{}' lack a group size", groups))),
+						Some(group_size) => parse_index(group_size)?,
+					};
+					if unlikely!(used == 0 || group_size == 0 || used > group_size)
+					{
+						return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Groups '{}' are invalid", groups)))
+					}
+					(used, group_size)
+				}
+			};
+
+			for logical_core_identifier in first ..= last
+			{
+				if (logical_core_identifier - first) % group_size < used
+				{
+					logical_cores.insert(logical_core_identifier);
+				}
+			}
+		}
+
+		Ok(Self(logical_cores))
+	}
+
+	/// Formats as a Linux list string, such as `0-3,8,10-11`, as used in `/proc/irq/<N>/smp_affinity_list`.
+	///
+	/// An empty set of logical cores is an empty string.
+	pub fn to_linux_list_string(&self) -> String
+	{
+		let mut linux_list_string = String::with_capacity(self.0.len() * 4);
+
+		let mut logical_core_identifiers = self.0.iter().cloned().peekable();
+		while let Some(first) = logical_core_identifiers.next()
+		{
+			let mut last = first;
+			while let Some(&next) = logical_core_identifiers.peek()
+			{
+				if last.checked_add(1) != Some(next)
+				{
+					break
+				}
+				last = next;
+				logical_core_identifiers.next();
+			}
+
+			if !linux_list_string.is_empty()
+			{
+				linux_list_string.push(',');
+			}
+			if first == last
+			{
+				linux_list_string.push_str(&format!("{}", first));
+			}
+			else
+			{
+				linux_list_string.push_str(&format!("{}-{}", first, last));
+			}
+		}
+
+		linux_list_string
+	}
+
+	/// Parses a Linux mask string, such as `ff,00000001`, as used in `/sys/class/net/<interface>/queues/rx-<N>/rps_cpus` and `/proc/irq/<N>/smp_affinity`.
+	///
+	/// The mask is hexadecimal, most significant 32-bit word first, with words separated by commas.
+	/// Leading and trailing whitespace (such as a final line feed) is ignored.
+	///
+	/// Fails with `InvalidData` if the string is malformed.
+	pub fn from_linux_mask_string(linux_mask_string: &str) -> io::Result<Self>
+	{
+		let mut logical_cores = BTreeSet::new();
+
+		let mut bit_offset: usize = 0;
+		for word in linux_mask_string.trim().rsplit(',')
+		{
+			if unlikely!(word.is_empty() || word.len() > 8)
+			{
+				return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Invalid mask word '{}'", word)))
+			}
+			let word = u32::from_str_radix(word, 16).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid mask word '{}'", word)))?;
+
+			for bit in 0 .. 32
+			{
+				if word & (1 << bit) != 0
+				{
+					let logical_core_identifier = bit_offset + bit;
+					if unlikely!(logical_core_identifier > (LogicalCoreIdentifier::MAX as usize))
+					{
+						return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Logical core {} is too large", logical_core_identifier)))
+					}
+					logical_cores.insert(logical_core_identifier as LogicalCoreIdentifier);
+				}
+			}
+			bit_offset += 32;
+		}
+
+		Ok(Self(logical_cores))
+	}
+
+	/// Formats as a Linux mask string, such as `ff,00000001`, as used in `/sys/class/net/<interface>/queues/rx-<N>/rps_cpus`.
+	///
+	/// Uses as few 32-bit words as possible; an empty set of logical cores is `0`.
+	pub fn to_linux_mask_string(&self) -> String
+	{
+		let number_of_words = match self.0.iter().next_back()
+		{
+			None => return "0".to_string(),
+			Some(&highest) => (highest as usize) / 32 + 1,
+		};
+
+		let mut words = vec![0u32; number_of_words];
+		for logical_core_identifier in self.0.iter()
+		{
+			let logical_core_identifier = *logical_core_identifier as usize;
+			words[logical_core_identifier / 32] |= 1 << (logical_core_identifier % 32);
+		}
+
+		words.iter().rev().map(|word| format!("{:08x}", word)).collect::<Vec<_>>().join(",")
+	}
+
+	/// Reads a file containing a Linux list string, such as `/sys/devices/system/cpu/online`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub(crate) fn read_linux_list_file(file_path: impl AsRef<Path>) -> io::Result<Self>
+	{
+		Self::from_linux_list_string(&read_to_string(file_path)?)
+	}
+
+	/// As `read_linux_list_file()`, but a missing file is an empty set of logical cores.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub(crate) fn read_optional_linux_list_file(file_path: impl AsRef<Path>) -> io::Result<Self>
+	{
+		match Self::read_linux_list_file(file_path)
+		{
+			Err(ref error) if error.kind() == io::ErrorKind::NotFound => Ok(Self(BTreeSet::new())),
+			result => result,
+		}
+	}
+
 	#[allow(dead_code)]
 	#[inline(always)]
 	fn last_os_error() -> io::Error
 	{
 		io::Error::last_os_error()
 	}
+
+	#[allow(dead_code)]
+	#[inline(always)]
+	fn invalid_data(message: String) -> io::Error
+	{
+		io::Error::new(io::ErrorKind::InvalidData, message)
+	}
+}
+
+/// Test fixture shared by the test modules of this crate.
+#[cfg(test)]
+fn logical_cores(logical_core_identifiers: &[LogicalCoreIdentifier]) -> LogicalCores
+{
+	LogicalCores::from(logical_core_identifiers.iter().cloned().collect::<BTreeSet<_>>())
 }
 
 #[cfg(any(target_os = "android", target_os = "emscripten", target_os = "fuschia", target_os = "linux", target_env = "uclibc"))] include!("LogicalCores.android-emscripten-fuschia-linux-uclibc.rs");
@@ -311,3 +870,144 @@ impl LogicalCores
 #[cfg(target_os = "netbsd")] include!("LogicalCores.netbsd.rs");
 #[cfg(not(any(target_os = "android", target_os = "dragonfly", target_os = "emscripten", target_os = "freebsd", target_os = "fuschia", target_os = "ios", target_os = "linux", target_os = "macos", target_os = "netbsd", target_env = "uclibc", windows)))] include!("LogicalCores.others.rs");
 #[cfg(windows)] include!("LogicalCores.windows.rs");
+
+
+#[cfg(test)]
+mod logical_cores_tests
+{
+	use super::*;
+
+	#[test]
+	fn from_linux_list_string_parses_indices_and_ranges()
+	{
+		assert_eq!(LogicalCores::from_linux_list_string("0-3,8,10-11\n").unwrap(), logical_cores(&[0, 1, 2, 3, 8, 10, 11]));
+		assert_eq!(LogicalCores::from_linux_list_string(" 5 ").unwrap(), logical_cores(&[5]));
+		assert_eq!(LogicalCores::from_linux_list_string("").unwrap(), logical_cores(&[]));
+		assert_eq!(LogicalCores::from_linux_list_string("\n").unwrap(), logical_cores(&[]));
+		assert_eq!(LogicalCores::from_linux_list_string("65534-65535").unwrap(), logical_cores(&[65534, 65535]));
+	}
+
+	#[test]
+	fn from_linux_list_string_parses_groups()
+	{
+		assert_eq!(LogicalCores::from_linux_list_string("0-7:2/4").unwrap(), logical_cores(&[0, 1, 4, 5]));
+		assert_eq!(LogicalCores::from_linux_list_string("2-9:1/3,20").unwrap(), logical_cores(&[2, 5, 8, 20]));
+	}
+
+	#[test]
+	fn from_linux_list_string_rejects_malformed()
+	{
+		for malformed in &["3-1", "a", "1-", "-1", "1,,2", "0-7:2", "0-7:0/4", "0-7:5/4", "0-7:2/0", "65536"]
+		{
+			assert_eq!(LogicalCores::from_linux_list_string(malformed).unwrap_err().kind(), io::ErrorKind::InvalidData, "{}", malformed);
+		}
+	}
+
+	#[test]
+	fn from_nohz_full_string_treats_null_as_empty()
+	{
+		assert_eq!(LogicalCores::from_nohz_full_string("(null)\n").unwrap(), logical_cores(&[]));
+		assert_eq!(LogicalCores::from_nohz_full_string("\n").unwrap(), logical_cores(&[]));
+		assert_eq!(LogicalCores::from_nohz_full_string("1-3\n").unwrap(), logical_cores(&[1, 2, 3]));
+		assert_eq!(LogicalCores::from_nohz_full_string("null").unwrap_err().kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn for_workloads_removes_housekeeping_logical_cores()
+	{
+		let isolated_or_nohz_full = LogicalCores::isolated_for_process().unwrap();
+		let pool = LogicalCores::valid_logical_cores_for_the_current_process();
+		let expected = logical_cores(&pool.iter().cloned().filter(|logical_core_identifier| !isolated_or_nohz_full.contains(logical_core_identifier)).collect::<Vec<_>>());
+
+		assert_eq!(LogicalCores::for_workloads(&HousekeepingPolicy::None).ok(), if expected.is_empty() { None } else { Some(expected) });
+		assert_eq!(LogicalCores::for_workloads(&HousekeepingPolicy::Explicit(pool)).unwrap_err().kind(), io::ErrorKind::Other);
+	}
+
+	#[test]
+	fn rcu_nocbs_parameter_is_last_before_init_arguments()
+	{
+		assert_eq!(LogicalCores::rcu_nocbs_parameter("quiet rcu_nocbs=1-3 nohz_full=1-3\n"), Some("1-3"));
+		assert_eq!(LogicalCores::rcu_nocbs_parameter("rcu_nocbs=1 quiet rcu_nocbs=all"), Some("all"));
+		assert_eq!(LogicalCores::rcu_nocbs_parameter("rcu_nocbs=2 -- rcu_nocbs=5"), Some("2"));
+		assert_eq!(LogicalCores::rcu_nocbs_parameter("quiet -- rcu_nocbs=5"), None);
+		assert_eq!(LogicalCores::rcu_nocbs_parameter("quiet rcu_nocbs --"), None);
+		assert_eq!(LogicalCores::rcu_nocbs_parameter(""), None);
+	}
+
+	#[test]
+	fn to_linux_list_string_coalesces_ranges()
+	{
+		assert_eq!(logical_cores(&[0, 1, 2, 3, 8, 10, 11]).to_linux_list_string(), "0-3,8,10-11");
+		assert_eq!(logical_cores(&[7]).to_linux_list_string(), "7");
+		assert_eq!(logical_cores(&[]).to_linux_list_string(), "");
+	}
+
+	#[test]
+	fn to_linux_list_string_does_not_overflow_at_highest_logical_core()
+	{
+		assert_eq!(logical_cores(&[65535]).to_linux_list_string(), "65535");
+		assert_eq!(logical_cores(&[1, 65533, 65534, 65535]).to_linux_list_string(), "1,65533-65535");
+	}
+
+	#[test]
+	fn from_linux_mask_string_parses_words_most_significant_first()
+	{
+		assert_eq!(LogicalCores::from_linux_mask_string("ff,00000001\n").unwrap(), logical_cores(&[0, 32, 33, 34, 35, 36, 37, 38, 39]));
+		assert_eq!(LogicalCores::from_linux_mask_string("8000000a").unwrap(), logical_cores(&[1, 3, 31]));
+		assert_eq!(LogicalCores::from_linux_mask_string("0").unwrap(), logical_cores(&[]));
+		assert_eq!(LogicalCores::from_linux_mask_string("00000000,00000000").unwrap(), logical_cores(&[]));
+		assert_eq!(LogicalCores::from_linux_mask_string(&format!("80000000{}", ",00000000".repeat(2047))).unwrap(), logical_cores(&[65535]));
+	}
+
+	#[test]
+	fn from_linux_mask_string_rejects_malformed()
+	{
+		for malformed in &["", "g", "1,,2", "123456789", ",1", "-1"]
+		{
+			assert_eq!(LogicalCores::from_linux_mask_string(malformed).unwrap_err().kind(), io::ErrorKind::InvalidData, "{}", malformed);
+		}
+		assert_eq!(LogicalCores::from_linux_mask_string(&format!("1{}", ",00000000".repeat(2048))).unwrap_err().kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn to_linux_mask_string_uses_as_few_words_as_possible()
+	{
+		assert_eq!(logical_cores(&[0, 32, 33, 34, 35, 36, 37, 38, 39]).to_linux_mask_string(), "000000ff,00000001");
+		assert_eq!(logical_cores(&[1, 3, 31]).to_linux_mask_string(), "8000000a");
+		assert_eq!(logical_cores(&[]).to_linux_mask_string(), "0");
+	}
+
+	#[test]
+	fn linux_mask_string_round_trips()
+	{
+		let original = logical_cores(&[0, 2, 3, 4, 9, 63, 64, 65535]);
+
+		assert_eq!(LogicalCores::from_linux_mask_string(&original.to_linux_mask_string()).unwrap(), original);
+	}
+
+	#[test]
+	fn linux_list_string_round_trips()
+	{
+		let original = logical_cores(&[0, 2, 3, 4, 9, 63, 64, 65535]);
+
+		assert_eq!(LogicalCores::from_linux_list_string(&original.to_linux_list_string()).unwrap(), original);
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn hybrid_core_types_by_capacity_is_none_for_homogeneous_capacities()
+	{
+		assert_eq!(LogicalCores::hybrid_core_types_by_capacity(BTreeMap::new()), None);
+		assert_eq!(LogicalCores::hybrid_core_types_by_capacity(vec![(0, 1024), (1, 1024), (2, 1024)].into_iter().collect()), None);
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn hybrid_core_types_by_capacity_splits_greatest_capacity_from_the_rest()
+	{
+		let capacities = vec![(0, 446), (1, 446), (2, 871), (3, 871), (4, 1024), (6, 1024)].into_iter().collect();
+
+		assert_eq!(LogicalCores::hybrid_core_types_by_capacity(capacities), Some((logical_cores(&[4, 6]), logical_cores(&[0, 1, 2, 3]))));
+	}
+}
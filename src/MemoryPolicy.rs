@@ -0,0 +1,179 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// NUMA memory policy, the memory counterpart of logical core affinity.
+///
+/// Can be applied to the current thread (using `set_mempolicy()`) or to a range of memory (using `mbind()`); libnuma is not required.
+///
+/// The NUMA nodes to use are usually derived from the logical cores a thread has affinity for using `LogicalCores::numa_nodes()`.
+///
+/// See <https://www.kernel.org/doc/Documentation/admin-guide/mm/numa_memory_policy.rst>.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MemoryPolicy
+{
+	/// Use the default policy; for a thread, this is usually `Local`; for a range of memory, this is the thread's policy.
+	Default,
+
+	/// Only allocate memory from these NUMA nodes.
+	///
+	/// Allocations fail (and can cause the out-of-memory killer to run) if these NUMA nodes have no free memory.
+	Bind(NumaNodes),
+
+	/// Prefer to allocate memory from this NUMA node, falling back to other NUMA nodes if it has no free memory.
+	Preferred(NumaNodeIdentifier),
+
+	/// Interleave allocations page-by-page across these NUMA nodes.
+	///
+	/// Useful for large, shared, read-mostly tables.
+	Interleave(NumaNodes),
+
+	/// Allocate memory on the NUMA node of the logical core which first touches it.
+	///
+	/// Since Linux 3.8.
+	Local,
+}
+
+impl Default for MemoryPolicy
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		MemoryPolicy::Default
+	}
+}
+
+impl MemoryPolicy
+{
+	/// Sets the memory policy of the current thread.
+	///
+	/// Only affects memory allocated after this call; memory already touched is not moved.
+	///
+	/// Failure occurs if a NUMA node does not exist or is not permitted by the current cpuset (`EINVAL`).
+	#[inline(always)]
+	pub fn set_for_current_thread(&self) -> io::Result<()>
+	{
+		let (mode, node_mask, maximum_node) = self.to_mode_and_node_mask();
+		let result = unsafe { set_mempolicy(mode, Self::node_mask_pointer(&node_mask), maximum_node) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(LogicalCores::last_os_error())
+		}
+	}
+
+	/// Gets the memory policy of the current thread.
+	///
+	/// Mode flags (eg `MPOL_F_STATIC_NODES`) are discarded.
+	///
+	/// Failure occurs with `InvalidData` if the policy is one introduced after Linux 5.0 (eg `MPOL_PREFERRED_MANY`).
+	#[inline(always)]
+	pub fn for_current_thread() -> io::Result<Self>
+	{
+		let mut mode = MPOL_DEFAULT;
+		let (mut node_mask, maximum_node) = NumaNodes::empty_node_mask();
+		let result = unsafe { get_mempolicy(&mut mode, node_mask.as_mut_ptr(), maximum_node, null_mut(), 0) };
+		if unlikely!(result != 0)
+		{
+			return Err(LogicalCores::last_os_error())
+		}
+
+		use self::MemoryPolicy::*;
+		let numa_nodes = NumaNodes::from_node_mask(&node_mask);
+		match mode & !MPOL_MODE_FLAGS
+		{
+			MPOL_DEFAULT => Ok(Default),
+
+			MPOL_BIND => Ok(Bind(numa_nodes)),
+
+			// An empty node mask is how Linux before 3.8 represented `Local`.
+			MPOL_PREFERRED => match numa_nodes.iter().next()
+			{
+				None => Ok(Local),
+				Some(numa_node) => Ok(Preferred(*numa_node)),
+			},
+
+			MPOL_INTERLEAVE => Ok(Interleave(numa_nodes)),
+
+			MPOL_LOCAL => Ok(Local),
+
+			_ => Err(io::Error::from(io::ErrorKind::InvalidData)),
+		}
+	}
+
+	/// Sets the memory policy of the pages in the range `address` to `address + length`.
+	///
+	/// If `move_existing_pages` is true, pages already touched which do not conform to this policy are moved; otherwise only memory touched after this call is affected.
+	///
+	/// Failure occurs if:-
+	///
+	/// * `address` is not page aligned or the range is not mapped (`EFAULT`, `EINVAL`);
+	/// * A NUMA node does not exist or is not permitted by the current cpuset (`EINVAL`);
+	/// * Existing pages could not be moved (`EIO`);
+	/// * There is insufficient kernel memory (`ENOMEM`).
+	///
+	/// # Safety
+	///
+	/// The caller must own the memory range `address` to `address + length` (eg it was obtained using `mmap()`), as the policy of any other mapping that overlaps it is changed too.
+	#[inline(always)]
+	pub unsafe fn set_for_memory_range(&self, address: *mut u8, length: usize, move_existing_pages: bool) -> io::Result<()>
+	{
+		let (mode, node_mask, maximum_node) = self.to_mode_and_node_mask();
+		let flags = if move_existing_pages
+		{
+			MPOL_MF_MOVE
+		}
+		else
+		{
+			0
+		};
+		let result = mbind(address as *mut c_void, length as c_ulong, mode, Self::node_mask_pointer(&node_mask), maximum_node, flags);
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(LogicalCores::last_os_error())
+		}
+	}
+
+	#[inline(always)]
+	fn to_mode_and_node_mask(&self) -> (c_int, Option<Vec<c_ulong>>, c_ulong)
+	{
+		use self::MemoryPolicy::*;
+
+		match *self
+		{
+			Default => (MPOL_DEFAULT, None, 0),
+
+			Bind(ref numa_nodes) => Self::with_node_mask(MPOL_BIND, numa_nodes),
+
+			Preferred(numa_node) => Self::with_node_mask(MPOL_PREFERRED, &NumaNodes::from(numa_node)),
+
+			Interleave(ref numa_nodes) => Self::with_node_mask(MPOL_INTERLEAVE, numa_nodes),
+
+			Local => (MPOL_LOCAL, None, 0),
+		}
+	}
+
+	#[inline(always)]
+	fn with_node_mask(mode: c_int, numa_nodes: &NumaNodes) -> (c_int, Option<Vec<c_ulong>>, c_ulong)
+	{
+		let (node_mask, maximum_node) = numa_nodes.to_node_mask();
+		(mode, Some(node_mask), maximum_node)
+	}
+
+	#[inline(always)]
+	fn node_mask_pointer(node_mask: &Option<Vec<c_ulong>>) -> *const c_ulong
+	{
+		match *node_mask
+		{
+			None => null(),
+			Some(ref node_mask) => node_mask.as_ptr(),
+		}
+	}
+}
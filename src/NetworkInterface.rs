@@ -0,0 +1,285 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A network interface, such as `eth0`, and the mapping of its queues to logical cores.
+///
+/// Mapping queues to the logical cores of per-core workers (eg those using `SO_INCOMING_CPU`) is needed so that packets are processed on the same logical core from interrupt to application.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NetworkInterface(pub String);
+
+impl NetworkInterface
+{
+	/// All network interfaces, in ascending order of name.
+	///
+	/// Slow as it will read the directory `/sys/class/net`.
+	pub fn all() -> io::Result<Vec<Self>>
+	{
+		let mut all = Vec::new();
+		for directory_entry in read_dir("/sys/class/net")?
+		{
+			if let Ok(name) = directory_entry?.file_name().into_string()
+			{
+				all.push(NetworkInterface(name))
+			}
+		}
+		all.sort();
+		Ok(all)
+	}
+
+	/// Indices of queues of a particular kind, in ascending order.
+	pub fn queue_indices(&self, kind: NetworkInterfaceQueueKind) -> io::Result<Vec<u16>>
+	{
+		let prefix = kind.folder_name_prefix();
+
+		let mut queue_indices = Vec::new();
+		for directory_entry in read_dir(self.file_path("queues"))?
+		{
+			if let Ok(file_name) = directory_entry?.file_name().into_string()
+			{
+				if let Some(Ok(queue_index)) = file_name.strip_prefix(prefix).map(u16::from_str)
+				{
+					queue_indices.push(queue_index)
+				}
+			}
+		}
+		queue_indices.sort();
+		Ok(queue_indices)
+	}
+
+	/// Receive Packet Steering (RPS) or Transmit Packet Steering (XPS) logical cores for a queue.
+	///
+	/// Empty if steering is disabled or not supported (eg `xps_cpus` does not exist for single queue devices).
+	pub fn steering(&self, kind: NetworkInterfaceQueueKind, queue_index: u16) -> io::Result<LogicalCores>
+	{
+		match read_to_string(self.steering_file_path(kind, queue_index))
+		{
+			Err(ref error) if error.kind() == io::ErrorKind::NotFound => Ok(LogicalCores::from(BTreeSet::<LogicalCoreIdentifier>::new())),
+			Err(error) => Err(error),
+			Ok(linux_mask_string) => LogicalCores::from_linux_mask_string(&linux_mask_string),
+		}
+	}
+
+	/// Sets Receive Packet Steering (RPS) or Transmit Packet Steering (XPS) logical cores for a queue; an empty set of logical cores disables steering.
+	///
+	/// Requires root or `CAP_NET_ADMIN`.
+	#[inline(always)]
+	pub fn set_steering(&self, kind: NetworkInterfaceQueueKind, queue_index: u16, logical_cores: &LogicalCores) -> io::Result<()>
+	{
+		OpenOptions::new().write(true).open(self.steering_file_path(kind, queue_index))?.write_all(logical_cores.to_linux_mask_string().as_bytes())
+	}
+
+	/// Sets the interrupt affinity of every interrupt thought to be for a queue.
+	///
+	/// Requires root or `CAP_SYS_ADMIN`.
+	pub fn set_interrupt_affinity(&self, kind: NetworkInterfaceQueueKind, queue_index: u16, logical_cores: &LogicalCores) -> io::Result<()>
+	{
+		for (interrupt_request_number, _) in self.queue_interrupts()?.into_iter().filter(|&(_, (queue_kind, index))| queue_kind.is_none_or(|queue_kind| queue_kind == kind) && index == queue_index)
+		{
+			IrqAffinity(interrupt_request_number).set_affinity(logical_cores)?;
+		}
+		Ok(())
+	}
+
+	/// All queues, receive queues first, with their steering and interrupts.
+	///
+	/// Slow as it will read several files in `/sys/class/net/<interface>/queues`, `/proc/interrupts` and `/proc/irq`.
+	pub fn queues(&self) -> io::Result<Vec<NetworkInterfaceQueue>>
+	{
+		let queue_interrupts = self.queue_interrupts()?;
+
+		let mut queues = Vec::new();
+		for &kind in [NetworkInterfaceQueueKind::Receive, NetworkInterfaceQueueKind::Transmit].iter()
+		{
+			for index in self.queue_indices(kind)?
+			{
+				let interrupt_request_numbers: Vec<InterruptRequestNumber> = queue_interrupts.iter().filter(|&(_, &(queue_kind, queue_index))| queue_kind.is_none_or(|queue_kind| queue_kind == kind) && queue_index == index).map(|(interrupt_request_number, _)| *interrupt_request_number).collect();
+
+				let mut interrupt_affinity: BTreeSet<LogicalCoreIdentifier> = BTreeSet::new();
+				for interrupt_request_number in interrupt_request_numbers.iter()
+				{
+					interrupt_affinity.extend(IrqAffinity(*interrupt_request_number).effective_or_requested_affinity()?.iter());
+				}
+
+				queues.push
+				(
+					NetworkInterfaceQueue
+					{
+						kind,
+						index,
+						steering: self.steering(kind, index)?,
+						interrupt_request_numbers,
+						interrupt_affinity: LogicalCores::from(interrupt_affinity),
+					}
+				)
+			}
+		}
+		Ok(queues)
+	}
+
+	/// Maps interrupts to queues; a kind of `None` is a combined receive and transmit queue (channel).
+	///
+	/// There is no kernel interface for this, so it is a heuristic based on common driver naming conventions (eg `eth0-TxRx-3`, `virtio1-input.3` and `mlx5_comp3@pci:0000:01:00.0`).
+	/// An interrupt is considered to be for this interface if it is one of the device's MSI interrupts or if its name contains the interface's or device's name; see `queue_for_interrupt_action()` for how the queue is found.
+	fn queue_interrupts(&self) -> io::Result<BTreeMap<InterruptRequestNumber, (Option<NetworkInterfaceQueueKind>, u16)>>
+	{
+		let device_path = self.file_path("device");
+
+		let mut names = vec![self.0.to_ascii_lowercase()];
+		let mut msi_interrupt_request_numbers = BTreeSet::new();
+		if let Ok(device_path) = device_path.canonicalize()
+		{
+			if let Some(device_name) = device_path.file_name().and_then(|file_name| file_name.to_str())
+			{
+				names.push(device_name.to_ascii_lowercase())
+			}
+
+			if let Ok(directory_entries) = read_dir(device_path.join("msi_irqs"))
+			{
+				for directory_entry in directory_entries
+				{
+					if let Some(interrupt_request_number) = directory_entry?.file_name().to_str().and_then(|file_name| InterruptRequestNumber::from_str(file_name).ok())
+					{
+						msi_interrupt_request_numbers.insert(interrupt_request_number);
+					}
+				}
+			}
+		}
+
+		let mut queue_interrupts = BTreeMap::new();
+		for interrupt in IrqAffinity::interrupts()?
+		{
+			let action = match Self::interrupt_action(&interrupt.description)
+			{
+				None => continue,
+				Some(action) => action,
+			};
+
+			let is_for_this_interface = msi_interrupt_request_numbers.contains(&interrupt.interrupt_request_number) || names.iter().any(|name| action.starts_with(&format!("{}-", name)) || action.contains(&format!("-{}-", name)));
+			if !is_for_this_interface
+			{
+				continue
+			}
+
+			if let Some(queue) = Self::queue_for_interrupt_action(&action, &names)
+			{
+				queue_interrupts.insert(interrupt.interrupt_request_number, queue);
+			}
+		}
+		Ok(queue_interrupts)
+	}
+
+	/// The action of an interrupt (the last word of its description in `/proc/interrupts`), lower-cased and without any `@` suffix (eg `mlx5_comp3@pci:0000:01:00.0` is `mlx5_comp3`).
+	#[inline(always)]
+	fn interrupt_action(description: &str) -> Option<String>
+	{
+		match description.rsplit(' ').next()
+		{
+			None | Some("") => None,
+			Some(action) => Some(action.split('@').next().unwrap().to_ascii_lowercase()),
+		}
+	}
+
+	/// The queue an interrupt is for, from its lower-cased `action`; `None` if it is not for a queue.
+	///
+	/// The queue index is the number at the end of `action`, which must follow a queue-style separator (eg `eth0-txrx-3`, `eth0-rx-3`, `virtio1-input.3` or `mlx5_comp3`).
+	/// Interrupts for asynchronous events, control or configuration (eg `mlx5_async0` or `virtio1-config`) and those named just for the interface or device (eg the link interrupt `eth0`) are not for a queue.
+	fn queue_for_interrupt_action(action: &str, names: &[String]) -> Option<(Option<NetworkInterfaceQueueKind>, u16)>
+	{
+		if names.iter().any(|name| action == name) || ["async", "ctrl", "config"].iter().any(|excluded| action.contains(excluded))
+		{
+			return None
+		}
+
+		let without_queue_index = action.trim_end_matches(|character: char| character.is_ascii_digit());
+		if without_queue_index.len() == action.len()
+		{
+			return None
+		}
+
+		let has_queue_separator = without_queue_index.ends_with('-') || without_queue_index.ends_with('.') || without_queue_index.ends_with("comp");
+		if !has_queue_separator
+		{
+			return None
+		}
+
+		let queue_index = match u16::from_str(&action[without_queue_index.len() .. ])
+		{
+			Err(_) => return None,
+			Ok(queue_index) => queue_index,
+		};
+
+		let kind = if without_queue_index.contains("txrx") || without_queue_index.contains("comp")
+		{
+			None
+		}
+		else if without_queue_index.contains("rx") || without_queue_index.contains("input")
+		{
+			Some(NetworkInterfaceQueueKind::Receive)
+		}
+		else if without_queue_index.contains("tx") || without_queue_index.contains("output")
+		{
+			Some(NetworkInterfaceQueueKind::Transmit)
+		}
+		else
+		{
+			None
+		};
+
+		Some((kind, queue_index))
+	}
+
+	#[inline(always)]
+	fn steering_file_path(&self, kind: NetworkInterfaceQueueKind, queue_index: u16) -> PathBuf
+	{
+		let mut file_path = self.file_path("queues");
+		file_path.push(format!("{}{}", kind.folder_name_prefix(), queue_index));
+		file_path.push(kind.steering_file_name());
+		file_path
+	}
+
+	#[inline(always)]
+	fn file_path(&self, file_name: &str) -> PathBuf
+	{
+		let mut file_path = PathBuf::from("/sys/class/net");
+		file_path.push(&self.0);
+		file_path.push(file_name);
+		file_path
+	}
+}
+
+#[cfg(test)]
+mod network_interface_tests
+{
+	use super::*;
+
+	fn queue(description: &str, names: &[&str]) -> Option<(Option<NetworkInterfaceQueueKind>, u16)>
+	{
+		let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+		NetworkInterface::queue_for_interrupt_action(&NetworkInterface::interrupt_action(description).unwrap(), &names)
+	}
+
+	#[test]
+	fn finds_queues_from_interrupt_names()
+	{
+		use self::NetworkInterfaceQueueKind::*;
+
+		assert_eq!(queue("IR-PCI-MSI 524289-edge eth0-TxRx-3", &["eth0", "0000:01:00.0"]), Some((None, 3)));
+		assert_eq!(queue("IR-PCI-MSI 524289-edge eth0-rx-12", &["eth0", "0000:01:00.0"]), Some((Some(Receive), 12)));
+		assert_eq!(queue("IR-PCI-MSI 524289-edge eth0-tx-0", &["eth0", "0000:01:00.0"]), Some((Some(Transmit), 0)));
+		assert_eq!(queue("PCI-MSIX-0000:00:03.0 1-edge virtio1-input.3", &["eth0", "virtio1"]), Some((Some(Receive), 3)));
+		assert_eq!(queue("PCI-MSIX-0000:00:03.0 2-edge virtio1-output.0", &["eth0", "virtio1"]), Some((Some(Transmit), 0)));
+		assert_eq!(queue("IR-PCI-MSIX-0000:01:00.0 4-edge mlx5_comp3@pci:0000:01:00.0", &["eth0", "0000:01:00.0"]), Some((None, 3)));
+	}
+
+	#[test]
+	fn ignores_interrupts_not_for_queues()
+	{
+		assert_eq!(queue("IR-PCI-MSI 524288-edge eth0", &["eth0", "0000:01:00.0"]), None);
+		assert_eq!(queue("IR-PCI-MSI 524288-edge 0000:01:00.0", &["eth0", "0000:01:00.0"]), None);
+		assert_eq!(queue("IR-PCI-MSIX-0000:01:00.0 0-edge mlx5_async0@pci:0000:01:00.0", &["eth0", "0000:01:00.0"]), None);
+		assert_eq!(queue("PCI-MSIX-0000:00:03.0 0-edge virtio1-config", &["eth0", "virtio1"]), None);
+		assert_eq!(queue("PCI-MSIX-0000:00:03.0 5-edge virtio1-ctrl.0", &["eth0", "virtio1"]), None);
+		assert_eq!(queue("IR-PCI-MSI 524288-edge i40e-0000:01:00.0:misc", &["eth0", "0000:01:00.0"]), None);
+	}
+}
@@ -0,0 +1,27 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A network interface queue and the logical cores which process its packets.
+///
+/// Produced by `NetworkInterface::queues()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkInterfaceQueue
+{
+	/// Receive or transmit.
+	pub kind: NetworkInterfaceQueueKind,
+
+	/// Index of the queue, eg `3` for `rx-3`.
+	pub index: u16,
+
+	/// For a receive queue, the logical cores that Receive Packet Steering (RPS) distributes packets to (`rps_cpus`); for a transmit queue, the logical cores which use it under Transmit Packet Steering (XPS) (`xps_cpus`).
+	///
+	/// Empty if steering is disabled or not supported.
+	pub steering: LogicalCores,
+
+	/// Interrupts which are thought to be for this queue.
+	pub interrupt_request_numbers: Vec<InterruptRequestNumber>,
+
+	/// The logical cores handling `interrupt_request_numbers`; for a receive queue with Receive Side Scaling (RSS), this is where packets are first processed.
+	pub interrupt_affinity: LogicalCores,
+}
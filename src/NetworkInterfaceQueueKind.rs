@@ -0,0 +1,43 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Whether a network interface queue receives or transmits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NetworkInterfaceQueueKind
+{
+	/// A receive queue (`rx-<N>`); its steering is Receive Packet Steering (RPS).
+	Receive,
+
+	/// A transmit queue (`tx-<N>`); its steering is Transmit Packet Steering (XPS).
+	Transmit,
+}
+
+impl NetworkInterfaceQueueKind
+{
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	fn folder_name_prefix(self) -> &'static str
+	{
+		use self::NetworkInterfaceQueueKind::*;
+
+		match self
+		{
+			Receive => "rx-",
+			Transmit => "tx-",
+		}
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	fn steering_file_name(self) -> &'static str
+	{
+		use self::NetworkInterfaceQueueKind::*;
+
+		match self
+		{
+			Receive => "rps_cpus",
+			Transmit => "xps_cpus",
+		}
+	}
+}
@@ -0,0 +1,269 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A `Box`-like owner of a value whose backing memory is placed on a particular NUMA node.
+///
+/// Memory is obtained directly from the Operating System using `mmap()` and bound to the NUMA node using `mbind()` before it is first touched; libnuma is not required.
+///
+/// Only the memory occupied directly by the value is placed; any heap memory the value itself owns (eg the contents of a `Vec`) is allocated as usual.
+/// To also place that memory, construct the value using `PerLogicalCoreData::new_on_each_core()`.
+///
+/// Memory is allocated in whole pages, so this is only sensible for large values such as ring buffers.
+pub struct NumaLocalBox<T>
+{
+	pointer: NonNull<T>,
+	mapped_length: usize,
+	numa_node: Option<NumaNodeIdentifier>,
+	marker: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Send for NumaLocalBox<T>
+{
+}
+
+unsafe impl<T: Sync> Sync for NumaLocalBox<T>
+{
+}
+
+impl<T> Drop for NumaLocalBox<T>
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		unsafe
+		{
+			drop_in_place(self.pointer.as_ptr());
+			self.unmap()
+		}
+	}
+}
+
+impl<T: Debug> Debug for NumaLocalBox<T>
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		self.deref().fmt(f)
+	}
+}
+
+impl<T> Deref for NumaLocalBox<T>
+{
+	type Target = T;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		unsafe { self.pointer.as_ref() }
+	}
+}
+
+impl<T> DerefMut for NumaLocalBox<T>
+{
+	#[inline(always)]
+	fn deref_mut(&mut self) -> &mut Self::Target
+	{
+		unsafe { self.pointer.as_mut() }
+	}
+}
+
+impl<T> AsRef<T> for NumaLocalBox<T>
+{
+	#[inline(always)]
+	fn as_ref(&self) -> &T
+	{
+		self.deref()
+	}
+}
+
+impl<T> AsMut<T> for NumaLocalBox<T>
+{
+	#[inline(always)]
+	fn as_mut(&mut self) -> &mut T
+	{
+		self.deref_mut()
+	}
+}
+
+impl<T> Borrow<T> for NumaLocalBox<T>
+{
+	#[inline(always)]
+	fn borrow(&self) -> &T
+	{
+		self.deref()
+	}
+}
+
+impl<T> BorrowMut<T> for NumaLocalBox<T>
+{
+	#[inline(always)]
+	fn borrow_mut(&mut self) -> &mut T
+	{
+		self.deref_mut()
+	}
+}
+
+impl<T> NumaLocalBox<T>
+{
+	/// Moves `value` into memory bound to `numa_node`.
+	///
+	/// Failure occurs if memory can not be mapped, or if `numa_node` does not exist or is not permitted by the current cpuset (`EINVAL`).
+	#[inline(always)]
+	pub fn new(value: T, numa_node: NumaNodeIdentifier) -> io::Result<Self>
+	{
+		Self::new_internal(value, Some(numa_node))
+	}
+
+	/// Moves `value` into memory bound to the NUMA node of `logical_core_identifier`.
+	///
+	/// If this is not a NUMA machine, or the logical core's NUMA node can not be determined, then the memory is not bound.
+	///
+	/// Slow as it will read the directory `/sys/devices/system/cpu/cpu<logical_core_identifier>`.
+	#[inline(always)]
+	pub fn new_for_logical_core(value: T, logical_core_identifier: LogicalCoreIdentifier) -> io::Result<Self>
+	{
+		Self::new_internal(value, LogicalCores::numa_node_for_logical_core(logical_core_identifier))
+	}
+
+	/// The NUMA node the memory is bound to, if any.
+	#[inline(always)]
+	pub fn numa_node(&self) -> Option<NumaNodeIdentifier>
+	{
+		self.numa_node
+	}
+
+	/// Moves the value out of NUMA-local memory and releases that memory.
+	#[inline(always)]
+	pub fn into_inner(self) -> T
+	{
+		let value = unsafe { read(self.pointer.as_ptr()) };
+		unsafe { self.unmap() };
+		forget(self);
+		value
+	}
+
+	#[inline(always)]
+	fn new_internal(value: T, numa_node: Option<NumaNodeIdentifier>) -> io::Result<Self>
+	{
+		let page_size = unsafe { sysconf(_SC_PAGESIZE) } as usize;
+		assert!(align_of::<T>() <= page_size, "Alignment of T exceeds the page size");
+
+		let size = max(size_of::<T>(), 1);
+		let mapped_length = size.div_ceil(page_size) * page_size;
+
+		let address = unsafe { mmap(null_mut(), mapped_length, PROT_READ | PROT_WRITE, MAP_PRIVATE | MAP_ANONYMOUS, -1, 0) };
+		if unlikely!(address == MAP_FAILED)
+		{
+			return Err(LogicalCores::last_os_error())
+		}
+
+		if let Some(numa_node) = numa_node
+		{
+			if let Err(error) = unsafe { MemoryPolicy::Bind(NumaNodes::from(numa_node)).set_for_memory_range(address as *mut u8, mapped_length, false) }
+			{
+				unsafe { munmap(address, mapped_length) };
+				return Err(error)
+			}
+		}
+
+		let pointer = address as *mut T;
+		unsafe { write(pointer, value) };
+
+		Ok
+		(
+			Self
+			{
+				pointer: unsafe { NonNull::new_unchecked(pointer) },
+				mapped_length,
+				numa_node,
+				marker: PhantomData,
+			}
+		)
+	}
+
+	#[inline(always)]
+	unsafe fn unmap(&self)
+	{
+		munmap(self.pointer.as_ptr() as *mut c_void, self.mapped_length);
+	}
+}
+
+
+#[cfg(test)]
+mod numa_local_box_tests
+{
+	use super::*;
+	use ::std::cell::Cell;
+	use ::std::rc::Rc;
+
+	const MPOL_F_NODE: c_ulong = 1 << 0;
+
+	const MPOL_F_ADDR: c_ulong = 1 << 1;
+
+	struct CountsDrops(Rc<Cell<usize>>);
+
+	impl Drop for CountsDrops
+	{
+		fn drop(&mut self)
+		{
+			self.0.set(self.0.get() + 1)
+		}
+	}
+
+	fn memory_policy_for_address<T>(numa_local_box: &NumaLocalBox<T>, flags: c_ulong) -> c_int
+	{
+		let mut mode = MPOL_DEFAULT;
+		let result = unsafe { get_mempolicy(&mut mode, null_mut(), 0, numa_local_box.deref() as *const T as *mut c_void, MPOL_F_ADDR | flags) };
+		assert_eq!(result, 0, "{}", io::Error::last_os_error());
+		mode
+	}
+
+	#[test]
+	fn new_binds_page_aligned_memory_to_numa_node()
+	{
+		let numa_local_box = NumaLocalBox::new([7u8; 5000], 0).unwrap();
+
+		assert_eq!(numa_local_box.numa_node(), Some(0));
+		assert_eq!(numa_local_box[4999], 7);
+		assert_eq!(numa_local_box.deref() as *const _ as usize % unsafe { sysconf(_SC_PAGESIZE) } as usize, 0);
+		assert_eq!(memory_policy_for_address(&numa_local_box, 0) & !MPOL_MODE_FLAGS, MPOL_BIND);
+		assert_eq!(memory_policy_for_address(&numa_local_box, MPOL_F_NODE), 0);
+	}
+
+	#[test]
+	fn new_for_logical_core_uses_numa_node_of_logical_core()
+	{
+		let logical_core_identifier = *LogicalCores::valid_logical_cores_for_the_current_process().iter().next().unwrap();
+
+		let numa_local_box = NumaLocalBox::new_for_logical_core(1u64, logical_core_identifier).unwrap();
+
+		assert_eq!(numa_local_box.numa_node(), LogicalCores::numa_node_for_logical_core(logical_core_identifier));
+		assert_eq!(*numa_local_box, 1);
+	}
+
+	#[test]
+	fn drop_drops_value_once()
+	{
+		let drops = Rc::new(Cell::new(0));
+
+		drop(NumaLocalBox::new(CountsDrops(drops.clone()), 0).unwrap());
+
+		assert_eq!(drops.get(), 1);
+	}
+
+	#[test]
+	fn into_inner_moves_value_out_without_dropping_it()
+	{
+		let drops = Rc::new(Cell::new(0));
+		let mut numa_local_box = NumaLocalBox::new((CountsDrops(drops.clone()), vec![1, 2]), 0).unwrap();
+		numa_local_box.1.push(3);
+
+		let (counts_drops, vector) = numa_local_box.into_inner();
+
+		assert_eq!(drops.get(), 0);
+		assert_eq!(vector, vec![1, 2, 3]);
+		drop(counts_drops);
+		assert_eq!(drops.get(), 1);
+	}
+}
@@ -0,0 +1,225 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// NUMA nodes to use for memory policy, abstracting away the Linux node mask.
+///
+/// Usually derived from a set of logical cores using `LogicalCores::numa_nodes()`.
+///
+/// Create using one of the `From` implementations.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct NumaNodes(BTreeSet<NumaNodeIdentifier>);
+
+impl From<NumaNodeIdentifier> for NumaNodes
+{
+	/// From a NUMA node.
+	#[inline(always)]
+	fn from(numa_node: NumaNodeIdentifier) -> Self
+	{
+		let mut numa_nodes = BTreeSet::new();
+		numa_nodes.insert(numa_node);
+		Self(numa_nodes)
+	}
+}
+
+impl From<BTreeSet<NumaNodeIdentifier>> for NumaNodes
+{
+	#[inline(always)]
+	fn from(numa_nodes: BTreeSet<NumaNodeIdentifier>) -> Self
+	{
+		Self(numa_nodes)
+	}
+}
+
+impl From<NumaNodes> for BTreeSet<NumaNodeIdentifier>
+{
+	#[inline(always)]
+	fn from(numa_nodes: NumaNodes) -> Self
+	{
+		numa_nodes.0
+	}
+}
+
+impl Deref for NumaNodes
+{
+	type Target = BTreeSet<NumaNodeIdentifier>;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		&self.0
+	}
+}
+
+impl DerefMut for NumaNodes
+{
+	#[inline(always)]
+	fn deref_mut(&mut self) -> &mut Self::Target
+	{
+		&mut self.0
+	}
+}
+
+impl AsRef<BTreeSet<NumaNodeIdentifier>> for NumaNodes
+{
+	#[inline(always)]
+	fn as_ref(&self) -> &BTreeSet<NumaNodeIdentifier>
+	{
+		&self.0
+	}
+}
+
+impl AsMut<BTreeSet<NumaNodeIdentifier>> for NumaNodes
+{
+	#[inline(always)]
+	fn as_mut(&mut self) -> &mut BTreeSet<NumaNodeIdentifier>
+	{
+		&mut self.0
+	}
+}
+
+impl Borrow<BTreeSet<NumaNodeIdentifier>> for NumaNodes
+{
+	#[inline(always)]
+	fn borrow(&self) -> &BTreeSet<NumaNodeIdentifier>
+	{
+		&self.0
+	}
+}
+
+impl BorrowMut<BTreeSet<NumaNodeIdentifier>> for NumaNodes
+{
+	#[inline(always)]
+	fn borrow_mut(&mut self) -> &mut BTreeSet<NumaNodeIdentifier>
+	{
+		&mut self.0
+	}
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl NumaNodes
+{
+	const BitsPerWord: usize = c_ulong::BITS as usize;
+
+	/// Large enough for the maximum number of NUMA nodes the Linux kernel can be configured with (`CONFIG_NODES_SHIFT` of 10).
+	const MaximumNodeMaskWords: usize = 1024 / Self::BitsPerWord;
+
+	/// Returns a node mask and the value of `maxnode` to pass with it to `mbind()`, `set_mempolicy()` and the like.
+	#[inline(always)]
+	pub(crate) fn to_node_mask(&self) -> (Vec<c_ulong>, c_ulong)
+	{
+		let number_of_words = match self.0.iter().next_back()
+		{
+			None => 1,
+			Some(highest_numa_node) => (*highest_numa_node as usize) / Self::BitsPerWord + 1,
+		};
+
+		let mut node_mask = vec![0 as c_ulong; number_of_words];
+		for numa_node in self.0.iter()
+		{
+			let numa_node = *numa_node as usize;
+			node_mask[numa_node / Self::BitsPerWord] |= 1 << (numa_node % Self::BitsPerWord);
+		}
+
+		let maximum_node = Self::maximum_node(number_of_words);
+		(node_mask, maximum_node)
+	}
+
+	/// Returns an empty node mask large enough to be filled in by `get_mempolicy()` and the value of `maxnode` to pass with it.
+	#[inline(always)]
+	pub(crate) fn empty_node_mask() -> (Vec<c_ulong>, c_ulong)
+	{
+		(vec![0 as c_ulong; Self::MaximumNodeMaskWords], Self::maximum_node(Self::MaximumNodeMaskWords))
+	}
+
+	/// Parses a node mask filled in by `get_mempolicy()`.
+	///
+	/// NUMA nodes which can not be represented by a `NumaNodeIdentifier` are ignored.
+	#[inline(always)]
+	pub(crate) fn from_node_mask(node_mask: &[c_ulong]) -> Self
+	{
+		let mut numa_nodes = BTreeSet::new();
+		for (word_index, word) in node_mask.iter().enumerate()
+		{
+			for bit in 0 .. Self::BitsPerWord
+			{
+				if word & (1 << bit) != 0
+				{
+					let numa_node = word_index * Self::BitsPerWord + bit;
+					if numa_node <= (NumaNodeIdentifier::MAX as usize)
+					{
+						numa_nodes.insert(numa_node as NumaNodeIdentifier);
+					}
+				}
+			}
+		}
+		Self(numa_nodes)
+	}
+
+	/// The Linux kernel ignores the last bit of `maxnode`, hence `+ 1`.
+	#[inline(always)]
+	fn maximum_node(number_of_words: usize) -> c_ulong
+	{
+		(number_of_words * Self::BitsPerWord + 1) as c_ulong
+	}
+}
+
+
+#[cfg(test)]
+mod numa_nodes_tests
+{
+	use super::*;
+
+	fn numa_nodes(numa_node_identifiers: &[NumaNodeIdentifier]) -> NumaNodes
+	{
+		NumaNodes::from(numa_node_identifiers.iter().cloned().collect::<BTreeSet<_>>())
+	}
+
+	#[test]
+	fn to_node_mask_sets_one_bit_per_numa_node()
+	{
+		let (node_mask, maximum_node) = numa_nodes(&[0, 3]).to_node_mask();
+
+		assert_eq!(node_mask, vec![0b1001]);
+		assert_eq!(maximum_node, (NumaNodes::BitsPerWord + 1) as c_ulong);
+	}
+
+	#[test]
+	fn to_node_mask_spans_words()
+	{
+		let highest_numa_node = NumaNodes::BitsPerWord as NumaNodeIdentifier + 1;
+
+		let (node_mask, maximum_node) = numa_nodes(&[1, highest_numa_node]).to_node_mask();
+
+		assert_eq!(node_mask, vec![0b10, 0b10]);
+		assert_eq!(maximum_node, (2 * NumaNodes::BitsPerWord + 1) as c_ulong);
+	}
+
+	#[test]
+	fn to_node_mask_of_no_numa_nodes_is_one_empty_word()
+	{
+		let (node_mask, _) = numa_nodes(&[]).to_node_mask();
+
+		assert_eq!(node_mask, vec![0]);
+	}
+
+	#[test]
+	fn from_node_mask_round_trips()
+	{
+		let original = numa_nodes(&[0, 2, 63, 64, 200, 255]);
+
+		let (node_mask, _) = original.to_node_mask();
+
+		assert_eq!(NumaNodes::from_node_mask(&node_mask), original);
+	}
+
+	#[test]
+	fn from_node_mask_ignores_numa_nodes_which_are_too_large()
+	{
+		let (mut node_mask, _) = NumaNodes::empty_node_mask();
+		node_mask[0] = 0b100;
+		node_mask[256 / NumaNodes::BitsPerWord] = 1;
+
+		assert_eq!(NumaNodes::from_node_mask(&node_mask), numa_nodes(&[2]));
+	}
+}
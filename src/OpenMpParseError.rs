@@ -0,0 +1,64 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// An error parsing an OpenMP `OMP_PLACES` or `OMP_PROC_BIND` specification.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OpenMpParseError
+{
+	/// The specification ended unexpectedly.
+	UnexpectedEnd,
+
+	/// An unexpected character was found at the byte position.
+	UnexpectedCharacter
+	{
+		/// Byte position.
+		position: usize,
+
+		/// Character.
+		character: char,
+	},
+
+	/// A number was too large, or a logical core computed from a stride was negative or too large.
+	OutOfRange
+	{
+		/// Byte position.
+		position: usize,
+	},
+
+	/// An abstract name (eg `cores`) is not one supported.
+	UnknownAbstractName(String),
+
+	/// A processor binding (eg `spread`) is not one supported.
+	UnknownProcessorBinding(String),
+
+	/// None of the places specified contain any logical cores in the pool.
+	NoPlacesAvailable,
+
+	/// A place list specified more places, counted by the logical cores they contain, than there are possible logical cores.
+	TooManyPlaces,
+}
+
+impl Display for OpenMpParseError
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::OpenMpParseError::*;
+
+		match *self
+		{
+			UnexpectedEnd => write!(f, "Unexpected end of specification"),
+			UnexpectedCharacter { position, character } => write!(f, "Unexpected character '{}' at position {}", character, position),
+			OutOfRange { position } => write!(f, "Number or computed logical core out of range at position {}", position),
+			UnknownAbstractName(ref name) => write!(f, "Unknown abstract name '{}'", name),
+			UnknownProcessorBinding(ref name) => write!(f, "Unknown processor binding '{}'", name),
+			NoPlacesAvailable => write!(f, "No places contain logical cores available to the process"),
+			TooManyPlaces => write!(f, "Place list specifies more places than there are possible logical cores"),
+		}
+	}
+}
+
+impl error::Error for OpenMpParseError
+{
+}
@@ -0,0 +1,217 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// An ordered list of OpenMP places, as specified by `OMP_PLACES`.
+///
+/// Each place is a non-empty set of logical cores; a thread bound to a place may run on any logical core in it.
+///
+/// Supports both abstract names (`threads`, `cores`, `ll_caches`, `numa_domains` and `sockets`, each optionally followed by a count, eg `cores(4)`) and explicit place lists (eg `{0:4},{4:4}`, `{0,1},{2,3}`, `{0}:8:2` or `{0:2,!1}`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenMpPlaces(Vec<LogicalCores>);
+
+impl Deref for OpenMpPlaces
+{
+	type Target = [LogicalCores];
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		&self.0[..]
+	}
+}
+
+impl From<OpenMpPlaces> for Vec<LogicalCores>
+{
+	#[inline(always)]
+	fn from(open_mp_places: OpenMpPlaces) -> Self
+	{
+		open_mp_places.0
+	}
+}
+
+impl OpenMpPlaces
+{
+	/// The name of the environment variable used by OpenMP runtimes.
+	pub const EnvironmentVariableName: &'static str = "OMP_PLACES";
+
+	/// The most logical cores, summed over all places, that a place list may specify; it is the number of possible logical cores.
+	///
+	/// This stops a place list such as `{0:65535}:65535:0` from consuming a huge amount of memory.
+	const MaximumLogicalCoresInPlaces: usize = LogicalCoreIdentifier::MAX as usize + 1;
+
+	/// Parses the `OMP_PLACES` environment variable.
+	///
+	/// If the environment variable is absent or empty, defaults to `threads`, as do common OpenMP runtimes.
+	#[inline(always)]
+	pub fn from_environment(pool: &LogicalCores, topology: &Topology) -> Result<Self, OpenMpParseError>
+	{
+		match var(Self::EnvironmentVariableName)
+		{
+			Ok(specification) => Self::parse(&specification, pool, topology),
+			Err(_) => Self::parse("", pool, topology),
+		}
+	}
+
+	/// Parses an `OMP_PLACES` specification.
+	///
+	/// Abstract names are resolved using `topology`.
+	/// Logical cores not in `pool` are removed from each place, and places which become empty are removed; if no places remain, an error is returned.
+	///
+	/// An empty specification is treated as `threads`.
+	#[inline(always)]
+	pub fn parse(specification: &str, pool: &LogicalCores, topology: &Topology) -> Result<Self, OpenMpParseError>
+	{
+		let mut cursor = OpenMpSpecificationCursor::new(specification);
+
+		let places = match cursor.peek()
+		{
+			None => Self::abstract_name_places("threads", pool, topology),
+
+			Some(byte) if byte.is_ascii_alphabetic() =>
+			{
+				let name = cursor.name()?;
+				let mut places = Self::abstract_name_places(&name, pool, topology)?;
+				if cursor.consume_if(b'(')
+				{
+					let count = cursor.unsigned()? as usize;
+					cursor.expect(b')')?;
+					places.truncate(count);
+				}
+				Ok(places)
+			}
+
+			Some(_) => Self::place_list(&mut cursor),
+		}?;
+
+		if !cursor.is_at_end()
+		{
+			return Err(cursor.unexpected())
+		}
+
+		let places: Vec<LogicalCores> = places.into_iter().filter_map(|place|
+		{
+			let place: BTreeSet<LogicalCoreIdentifier> = place.intersection(pool).cloned().collect();
+			if place.is_empty()
+			{
+				None
+			}
+			else
+			{
+				Some(LogicalCores::from(place))
+			}
+		}).collect();
+
+		if places.is_empty()
+		{
+			Err(OpenMpParseError::NoPlacesAvailable)
+		}
+		else
+		{
+			Ok(OpenMpPlaces(places))
+		}
+	}
+
+	#[inline(always)]
+	fn abstract_name_places(name: &str, pool: &LogicalCores, topology: &Topology) -> Result<Vec<LogicalCores>, OpenMpParseError>
+	{
+		#[inline(always)]
+		fn values<Key>(groups: BTreeMap<Key, LogicalCores>) -> Vec<LogicalCores>
+		{
+			groups.into_values().collect()
+		}
+
+		let places = match name
+		{
+			"threads" => pool.iter().map(|logical_core_identifier| LogicalCores::from(*logical_core_identifier)).collect(),
+			"cores" => values(topology.physical_cores(pool)),
+			"ll_caches" => values(topology.last_level_caches(pool)),
+			"numa_domains" => values(topology.numa_nodes(pool)),
+			"sockets" => values(topology.packages(pool)),
+			_ => return Err(OpenMpParseError::UnknownAbstractName(name.to_string())),
+		};
+		Ok(places)
+	}
+
+	/// `place-list := place-interval (',' place-interval)*` where `place-interval := '!' place | place (':' length (':' stride)?)?`.
+	#[inline(always)]
+	fn place_list(cursor: &mut OpenMpSpecificationCursor) -> Result<Vec<LogicalCores>, OpenMpParseError>
+	{
+		let mut places = Vec::new();
+		let mut excluded = Vec::new();
+		let mut logical_cores_in_places: usize = 0;
+		loop
+		{
+			if cursor.consume_if(b'!')
+			{
+				excluded.push(Self::place(cursor)?);
+			}
+			else
+			{
+				let place = Self::place(cursor)?;
+				let (length, stride) = cursor.length_and_stride()?;
+
+				logical_cores_in_places = logical_cores_in_places.saturating_add((length as usize).saturating_mul(place.len().max(1)));
+				if unlikely!(logical_cores_in_places > Self::MaximumLogicalCoresInPlaces)
+				{
+					return Err(OpenMpParseError::TooManyPlaces)
+				}
+
+				for index in 0 .. length
+				{
+					let offset = index * stride;
+					let mut shifted = BTreeSet::new();
+					for logical_core_identifier in place.iter()
+					{
+						shifted.insert(cursor.logical_core_identifier((*logical_core_identifier as i64) + offset)?);
+					}
+					places.push(LogicalCores::from(shifted));
+				}
+			}
+
+			if !cursor.consume_if(b',')
+			{
+				break
+			}
+		}
+
+		places.retain(|place| !excluded.contains(place));
+		Ok(places)
+	}
+
+	/// `place := '{' resource-interval (',' resource-interval)* '}'` where `resource-interval := '!' resource | resource (':' length (':' stride)?)?`.
+	#[inline(always)]
+	fn place(cursor: &mut OpenMpSpecificationCursor) -> Result<LogicalCores, OpenMpParseError>
+	{
+		cursor.expect(b'{')?;
+
+		let mut included = BTreeSet::new();
+		let mut excluded = BTreeSet::new();
+		loop
+		{
+			if cursor.consume_if(b'!')
+			{
+				let resource = cursor.unsigned()?;
+				excluded.insert(cursor.logical_core_identifier(resource)?);
+			}
+			else
+			{
+				let resource = cursor.unsigned()?;
+				let (length, stride) = cursor.length_and_stride()?;
+				for index in 0 .. length
+				{
+					included.insert(cursor.logical_core_identifier(resource + index * stride)?);
+				}
+			}
+
+			if !cursor.consume_if(b',')
+			{
+				break
+			}
+		}
+
+		cursor.expect(b'}')?;
+
+		Ok(LogicalCores::from(included.difference(&excluded).cloned().collect::<BTreeSet<_>>()))
+	}
+}
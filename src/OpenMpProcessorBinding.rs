@@ -0,0 +1,256 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// An OpenMP thread affinity policy, as specified by `OMP_PROC_BIND`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OpenMpProcessorBinding
+{
+	/// Threads are not bound; each may run on any logical core in any place.
+	False,
+
+	/// Threads are bound, but the policy is implementation defined; treated as `Close`, as do common OpenMP runtimes.
+	True,
+
+	/// All threads are bound to the same place as the primary thread.
+	///
+	/// Also known as `master`.
+	Primary,
+
+	/// Threads are bound to places close to the place of the primary thread, in turn.
+	Close,
+
+	/// Threads are bound to places spread evenly across the places.
+	Spread,
+}
+
+impl Default for OpenMpProcessorBinding
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		OpenMpProcessorBinding::False
+	}
+}
+
+impl FromStr for OpenMpProcessorBinding
+{
+	type Err = OpenMpParseError;
+
+	/// Case insensitive.
+	#[inline(always)]
+	fn from_str(value: &str) -> Result<Self, Self::Err>
+	{
+		use self::OpenMpProcessorBinding::*;
+
+		let name = value.trim().to_ascii_lowercase();
+		let processor_binding = match &name[..]
+		{
+			"false" => False,
+			"true" => True,
+			"primary" | "master" => Primary,
+			"close" => Close,
+			"spread" => Spread,
+			_ => return Err(OpenMpParseError::UnknownProcessorBinding(name)),
+		};
+		Ok(processor_binding)
+	}
+}
+
+impl OpenMpProcessorBinding
+{
+	/// The name of the environment variable used by OpenMP runtimes.
+	pub const EnvironmentVariableName: &'static str = "OMP_PROC_BIND";
+
+	/// Parses the `OMP_PROC_BIND` environment variable.
+	///
+	/// If the environment variable is absent or empty, defaults to `[False]`.
+	#[inline(always)]
+	pub fn from_environment() -> Result<Vec<Self>, OpenMpParseError>
+	{
+		match var(Self::EnvironmentVariableName)
+		{
+			Ok(specification) => Self::parse_list(&specification),
+			Err(_) => Self::parse_list(""),
+		}
+	}
+
+	/// Parses an `OMP_PROC_BIND` specification, which is a comma-separated list of policies, one for each level of nested parallelism.
+	///
+	/// An empty specification is treated as `false`.
+	#[inline(always)]
+	pub fn parse_list(specification: &str) -> Result<Vec<Self>, OpenMpParseError>
+	{
+		if specification.trim().is_empty()
+		{
+			return Ok(vec![OpenMpProcessorBinding::False])
+		}
+
+		specification.split(',').map(Self::from_str).collect()
+	}
+
+	/// Assigns `number_of_threads` threads to `places`, returning the logical cores each thread may run on.
+	///
+	/// The primary thread is thread 0 and runs in the place at `primary_place_index`; the set of logical cores for thread `n` is at index `n` in the result.
+	///
+	/// Follows the OpenMP specification's rules for `close`, `spread` and `primary`, including when there are more threads than places.
+	#[inline(always)]
+	pub fn assign(self, places: &OpenMpPlaces, primary_place_index: usize, number_of_threads: usize) -> Vec<LogicalCores>
+	{
+		use self::OpenMpProcessorBinding::*;
+
+		if unlikely!(number_of_threads == 0)
+		{
+			return Vec::new()
+		}
+
+		let number_of_places = places.len();
+		let place = |index: usize| places[(primary_place_index + index) % number_of_places].clone();
+
+		let mut assignment = Vec::with_capacity(number_of_threads);
+		match self
+		{
+			False =>
+			{
+				let all = LogicalCores::from(places.iter().flat_map(|place| place.iter().cloned()).collect::<BTreeSet<_>>());
+				assignment.resize(number_of_threads, all)
+			}
+
+			Primary => assignment.resize(number_of_threads, place(0)),
+
+			True | Close if number_of_threads <= number_of_places => assignment.extend((0 .. number_of_threads).map(place)),
+
+			Spread if number_of_threads <= number_of_places =>
+			{
+				let (subpartition_size, remainder) = (number_of_places / number_of_threads, number_of_places % number_of_threads);
+				let mut place_index = 0;
+				for thread_index in 0 .. number_of_threads
+				{
+					assignment.push(place(place_index));
+					place_index += subpartition_size + if thread_index < remainder { 1 } else { 0 };
+				}
+			}
+
+			True | Close | Spread =>
+			{
+				let (threads_per_place, remainder) = (number_of_threads / number_of_places, number_of_threads % number_of_places);
+				for place_index in 0 .. number_of_places
+				{
+					let threads_in_place = threads_per_place + if place_index < remainder { 1 } else { 0 };
+					assignment.resize(assignment.len() + threads_in_place, place(place_index))
+				}
+			}
+		}
+		assignment
+	}
+}
+
+#[cfg(test)]
+mod open_mp_processor_binding_tests
+{
+	use super::*;
+
+	/// Two physical cores, each with two simultaneous multi-threading (SMT) siblings, in one package.
+	fn pool_and_topology() -> (LogicalCores, Topology)
+	{
+		let pool = logical_cores(&[0, 1, 2, 3]);
+		let topology = Topology::from(PerLogicalCoreData::new(&pool, |logical_core_identifier| LogicalCoreTopology
+		{
+			package: 0,
+			numa_node: 0,
+			last_level_cache: 0,
+			physical_core: logical_core_identifier / 2,
+		}));
+		(pool, topology)
+	}
+
+	fn places(specification: &str) -> OpenMpPlaces
+	{
+		let (pool, topology) = pool_and_topology();
+		OpenMpPlaces::parse(specification, &pool, &topology).unwrap()
+	}
+
+	#[test]
+	fn parses_abstract_names()
+	{
+		assert_eq!(&places("")[..], &[logical_cores(&[0]), logical_cores(&[1]), logical_cores(&[2]), logical_cores(&[3])][..]);
+		assert_eq!(&places("cores")[..], &[logical_cores(&[0, 1]), logical_cores(&[2, 3])][..]);
+		assert_eq!(&places("CORES(1)")[..], &[logical_cores(&[0, 1])][..]);
+		assert_eq!(&places("sockets")[..], &[logical_cores(&[0, 1, 2, 3])][..]);
+
+		let (pool, topology) = pool_and_topology();
+		assert_eq!(OpenMpPlaces::parse("widgets", &pool, &topology), Err(OpenMpParseError::UnknownAbstractName("widgets".to_string())));
+	}
+
+	#[test]
+	fn parses_explicit_places()
+	{
+		assert_eq!(&places("{0:2},{2:2}")[..], &[logical_cores(&[0, 1]), logical_cores(&[2, 3])][..]);
+		assert_eq!(&places("{0,1}, {2,3}")[..], &[logical_cores(&[0, 1]), logical_cores(&[2, 3])][..]);
+		assert_eq!(&places("{0}:4:1")[..], &[logical_cores(&[0]), logical_cores(&[1]), logical_cores(&[2]), logical_cores(&[3])][..]);
+		assert_eq!(&places("{0:2:2}")[..], &[logical_cores(&[0, 2])][..]);
+		assert_eq!(&places("{0:3,!1}")[..], &[logical_cores(&[0, 2])][..]);
+		assert_eq!(&places("{0}:4,!{1}")[..], &[logical_cores(&[0]), logical_cores(&[2]), logical_cores(&[3])][..]);
+		assert_eq!(&places("{0,8},{9}")[..], &[logical_cores(&[0])][..]);
+	}
+
+	#[test]
+	fn rejects_invalid_places()
+	{
+		let (pool, topology) = pool_and_topology();
+		assert_eq!(OpenMpPlaces::parse("{0", &pool, &topology), Err(OpenMpParseError::UnexpectedEnd));
+		assert_eq!(OpenMpPlaces::parse("{0}x", &pool, &topology), Err(OpenMpParseError::UnexpectedCharacter { position: 3, character: 'x' }));
+		assert_eq!(OpenMpPlaces::parse("{1}:2:-2", &pool, &topology), Err(OpenMpParseError::OutOfRange { position: 8 }));
+		assert_eq!(OpenMpPlaces::parse("{8}", &pool, &topology), Err(OpenMpParseError::NoPlacesAvailable));
+	}
+
+	#[test]
+	fn rejects_too_many_places()
+	{
+		let (pool, topology) = pool_and_topology();
+		assert_eq!(OpenMpPlaces::parse("{0:65535}:65535:0", &pool, &topology), Err(OpenMpParseError::TooManyPlaces));
+		assert_eq!(OpenMpPlaces::parse("{0,!0}:65535:0,{0}:2", &pool, &topology), Err(OpenMpParseError::TooManyPlaces));
+		assert_eq!(places("{0:4}:16384:0").len(), 16384);
+	}
+
+	#[test]
+	fn parses_processor_bindings()
+	{
+		use self::OpenMpProcessorBinding::*;
+
+		assert_eq!(OpenMpProcessorBinding::parse_list(""), Ok(vec![False]));
+		assert_eq!(OpenMpProcessorBinding::parse_list("TRUE"), Ok(vec![True]));
+		assert_eq!(OpenMpProcessorBinding::parse_list("spread, close,master,primary"), Ok(vec![Spread, Close, Primary, Primary]));
+		assert_eq!(OpenMpProcessorBinding::parse_list("sideways"), Err(OpenMpParseError::UnknownProcessorBinding("sideways".to_string())));
+	}
+
+	#[test]
+	fn assigns_no_threads()
+	{
+		use self::OpenMpProcessorBinding::*;
+
+		let places = places("threads");
+		for binding in &[False, True, Primary, Close, Spread]
+		{
+			assert_eq!(binding.assign(&places, 0, 0), Vec::new());
+		}
+	}
+
+	#[test]
+	fn assigns_threads()
+	{
+		use self::OpenMpProcessorBinding::*;
+
+		let places = places("threads");
+		let place = |logical_core_identifier| logical_cores(&[logical_core_identifier]);
+
+		assert_eq!(False.assign(&places, 0, 2), vec![logical_cores(&[0, 1, 2, 3]); 2]);
+		assert_eq!(Primary.assign(&places, 1, 3), vec![place(1); 3]);
+		assert_eq!(Close.assign(&places, 1, 3), vec![place(1), place(2), place(3)]);
+		assert_eq!(Close.assign(&places, 3, 2), vec![place(3), place(0)]);
+		assert_eq!(Spread.assign(&places, 0, 2), vec![place(0), place(2)]);
+		assert_eq!(Spread.assign(&places, 0, 3), vec![place(0), place(2), place(3)]);
+		assert_eq!(Close.assign(&places, 0, 6), vec![place(0), place(0), place(1), place(1), place(2), place(3)]);
+		assert_eq!(Spread.assign(&places, 0, 5), vec![place(0), place(0), place(1), place(2), place(3)]);
+	}
+}
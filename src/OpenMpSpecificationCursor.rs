@@ -0,0 +1,188 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+struct OpenMpSpecificationCursor<'a>
+{
+	specification: &'a [u8],
+	position: usize,
+}
+
+impl<'a> OpenMpSpecificationCursor<'a>
+{
+	#[inline(always)]
+	fn new(specification: &'a str) -> Self
+	{
+		Self
+		{
+			specification: specification.as_bytes(),
+			position: 0,
+		}
+	}
+
+	#[inline(always)]
+	fn is_at_end(&mut self) -> bool
+	{
+		self.peek().is_none()
+	}
+
+	/// Skips whitespace.
+	#[inline(always)]
+	fn peek(&mut self) -> Option<u8>
+	{
+		while let Some(&byte) = self.specification.get(self.position)
+		{
+			if byte.is_ascii_whitespace()
+			{
+				self.position += 1;
+			}
+			else
+			{
+				return Some(byte)
+			}
+		}
+		None
+	}
+
+	#[inline(always)]
+	fn consume_if(&mut self, expected: u8) -> bool
+	{
+		if self.peek() == Some(expected)
+		{
+			self.position += 1;
+			true
+		}
+		else
+		{
+			false
+		}
+	}
+
+	#[inline(always)]
+	fn expect(&mut self, expected: u8) -> Result<(), OpenMpParseError>
+	{
+		if self.consume_if(expected)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(self.unexpected())
+		}
+	}
+
+	#[inline(always)]
+	fn unexpected(&mut self) -> OpenMpParseError
+	{
+		match self.peek()
+		{
+			None => OpenMpParseError::UnexpectedEnd,
+			Some(byte) => OpenMpParseError::UnexpectedCharacter { position: self.position, character: byte as char },
+		}
+	}
+
+	#[inline(always)]
+	fn out_of_range(&self) -> OpenMpParseError
+	{
+		OpenMpParseError::OutOfRange { position: self.position }
+	}
+
+	/// Parses a name made of ASCII letters and underscores, lower-casing it.
+	#[inline(always)]
+	fn name(&mut self) -> Result<String, OpenMpParseError>
+	{
+		let start = match self.peek()
+		{
+			Some(byte) if byte.is_ascii_alphabetic() => self.position,
+			_ => return Err(self.unexpected()),
+		};
+
+		while let Some(&byte) = self.specification.get(self.position)
+		{
+			if byte.is_ascii_alphabetic() || byte == b'_'
+			{
+				self.position += 1;
+			}
+			else
+			{
+				break
+			}
+		}
+
+		Ok(String::from_utf8_lossy(&self.specification[start .. self.position]).to_ascii_lowercase())
+	}
+
+	#[inline(always)]
+	fn unsigned(&mut self) -> Result<i64, OpenMpParseError>
+	{
+		match self.peek()
+		{
+			Some(byte) if byte.is_ascii_digit() => (),
+			_ => return Err(self.unexpected()),
+		};
+
+		let mut value: i64 = 0;
+		while let Some(&byte) = self.specification.get(self.position)
+		{
+			if !byte.is_ascii_digit()
+			{
+				break
+			}
+
+			value = value * 10 + ((byte - b'0') as i64);
+			if value > (LogicalCoreIdentifier::MAX as i64)
+			{
+				return Err(self.out_of_range())
+			}
+			self.position += 1;
+		}
+		Ok(value)
+	}
+
+	#[inline(always)]
+	fn signed(&mut self) -> Result<i64, OpenMpParseError>
+	{
+		if self.consume_if(b'-')
+		{
+			Ok(-self.unsigned()?)
+		}
+		else
+		{
+			self.unsigned()
+		}
+	}
+
+	/// Parses an optional `:length` or `:length:stride` suffix; defaults to a length of 1 and a stride of 1.
+	#[inline(always)]
+	fn length_and_stride(&mut self) -> Result<(i64, i64), OpenMpParseError>
+	{
+		if !self.consume_if(b':')
+		{
+			return Ok((1, 1))
+		}
+
+		let length = self.unsigned()?;
+		let stride = if self.consume_if(b':')
+		{
+			self.signed()?
+		}
+		else
+		{
+			1
+		};
+		Ok((length, stride))
+	}
+
+	#[inline(always)]
+	fn logical_core_identifier(&self, value: i64) -> Result<LogicalCoreIdentifier, OpenMpParseError>
+	{
+		if value < 0 || value > (LogicalCoreIdentifier::MAX as i64)
+		{
+			Err(self.out_of_range())
+		}
+		else
+		{
+			Ok(value as LogicalCoreIdentifier)
+		}
+	}
+}
@@ -0,0 +1,18 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Packages (sockets) as a `Domain`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackageDomain;
+
+impl Domain for PackageDomain
+{
+	type Identifier = PackageIdentifier;
+
+	#[inline(always)]
+	fn domain_for_logical_core(logical_core_identifier: LogicalCoreIdentifier) -> Option<Self::Identifier>
+	{
+		LogicalCores::package_for_logical_core(logical_core_identifier)
+	}
+}
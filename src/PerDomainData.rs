@@ -0,0 +1,125 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Data with an item per domain (eg NUMA node or package) in use by the process.
+///
+/// Useful for read-mostly data that would otherwise be duplicated for each logical core.
+///
+/// The domain of each logical core is discovered once, at construction, so that `get_for_current_core()` is cheap.
+/// Logical cores whose domain can not be determined (eg NUMA nodes on a machine which is not NUMA) are assumed to be in the default domain (eg NUMA node 0).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PerDomainData<D: Domain, PerDomain>
+{
+	domains_data: BTreeMap<D::Identifier, PerDomain>,
+	logical_cores_to_domains: PerLogicalCoreData<D::Identifier>,
+}
+
+impl<D: Domain, PerDomain> PerDomainData<D, PerDomain>
+{
+	/// `constructor` is called once for each domain that at least one of `logical_cores` is in; it is passed the domain's identifier and those of `logical_cores` in it.
+	#[inline(always)]
+	pub fn new(logical_cores: &LogicalCores, mut constructor: impl FnMut(D::Identifier, &LogicalCores) -> PerDomain) -> Self
+	{
+		let logical_cores_to_domains = PerLogicalCoreData::new(logical_cores, |logical_core_identifier| D::domain_for_logical_core(logical_core_identifier).unwrap_or_default());
+
+		let mut logical_cores_by_domain: BTreeMap<D::Identifier, BTreeSet<LogicalCoreIdentifier>> = BTreeMap::new();
+		for (logical_core_identifier, domain) in logical_cores_to_domains.iter()
+		{
+			logical_cores_by_domain.entry(*domain).or_insert_with(BTreeSet::new).insert(logical_core_identifier);
+		}
+
+		let mut domains_data = BTreeMap::new();
+		for (domain, logical_cores_in_domain) in logical_cores_by_domain
+		{
+			domains_data.insert(domain, constructor(domain, &LogicalCores::from(logical_cores_in_domain)));
+		}
+
+		Self
+		{
+			domains_data,
+			logical_cores_to_domains,
+		}
+	}
+
+	/// Gets the data for a particular domain.
+	#[inline(always)]
+	pub fn get(&self, domain: D::Identifier) -> Option<&PerDomain>
+	{
+		self.domains_data.get(&domain)
+	}
+
+	/// Gets the mutable data for a particular domain.
+	#[inline(always)]
+	pub fn get_mut(&mut self, domain: D::Identifier) -> Option<&mut PerDomain>
+	{
+		self.domains_data.get_mut(&domain)
+	}
+
+	/// The domain a particular logical core was found to be in at construction.
+	///
+	/// Returns `None` if the logical core was not one of those used at construction.
+	#[inline(always)]
+	pub fn domain_for_logical_core(&self, logical_core_identifier: LogicalCoreIdentifier) -> Option<D::Identifier>
+	{
+		self.logical_cores_to_domains.get(logical_core_identifier).cloned()
+	}
+
+	/// Gets the data for the domain of a particular logical core.
+	///
+	/// Returns `None` if the logical core was not one of those used at construction.
+	#[inline(always)]
+	pub fn get_for_logical_core(&self, logical_core_identifier: LogicalCoreIdentifier) -> Option<&PerDomain>
+	{
+		match self.domain_for_logical_core(logical_core_identifier)
+		{
+			None => None,
+			Some(domain) => self.get(domain),
+		}
+	}
+
+	/// Gets the mutable data for the domain of a particular logical core.
+	///
+	/// Returns `None` if the logical core was not one of those used at construction.
+	#[inline(always)]
+	pub fn get_mut_for_logical_core(&mut self, logical_core_identifier: LogicalCoreIdentifier) -> Option<&mut PerDomain>
+	{
+		match self.domain_for_logical_core(logical_core_identifier)
+		{
+			None => None,
+			Some(domain) => self.get_mut(domain),
+		}
+	}
+
+	/// Gets the data for the domain of `LogicalCores::current_logical_core()`.
+	///
+	/// Returns `None` if the current logical core was not one of those used at construction; this can happen if the current thread's affinity has not been set.
+	#[inline(always)]
+	pub fn get_for_current_core(&self) -> Option<&PerDomain>
+	{
+		self.get_for_logical_core(LogicalCores::current_logical_core())
+	}
+
+	/// Gets the mutable data for the domain of `LogicalCores::current_logical_core()`.
+	///
+	/// Returns `None` if the current logical core was not one of those used at construction; this can happen if the current thread's affinity has not been set.
+	#[inline(always)]
+	pub fn get_mut_for_current_core(&mut self) -> Option<&mut PerDomain>
+	{
+		self.get_mut_for_logical_core(LogicalCores::current_logical_core())
+	}
+
+	/// Iterates over the domains and their data, in ascending order of domain.
+	#[inline(always)]
+	pub fn iter<'a>(&'a self) -> impl Iterator<Item=(D::Identifier, &'a PerDomain)> + 'a
+	{
+		self.domains_data.iter().map(|(domain, per_domain)| (*domain, per_domain))
+	}
+
+	/// Mutably iterates over the domains and their data, in ascending order of domain.
+	#[inline(always)]
+	pub fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item=(D::Identifier, &'a mut PerDomain)> + 'a
+	{
+		self.domains_data.iter_mut().map(|(domain, per_domain)| (*domain, per_domain))
+	}
+}
@@ -49,25 +49,116 @@ impl<PerLogicalCore> IndexMut<LogicalCoreIdentifier> for PerLogicalCoreData<PerL
 	}
 }
 
-impl<PerLogicalCore> PerLogicalCoreData<PerLogicalCore>
+impl<PerLogicalCore> IntoIterator for PerLogicalCoreData<PerLogicalCore>
 {
-	/// Creates an empty set of logical core data.
+	type Item = (LogicalCoreIdentifier, PerLogicalCore);
+
+	type IntoIter = PerLogicalCoreDataIntoIterator<PerLogicalCore>;
+
 	#[inline(always)]
-	pub fn empty(logical_cores: &LogicalCores) -> Self
+	fn into_iter(self) -> Self::IntoIter
+	{
+		PerLogicalCoreDataIntoIterator(self.logical_cores_data.into_vec().into_iter().enumerate())
+	}
+}
+
+impl<'a, PerLogicalCore: 'a> IntoIterator for &'a PerLogicalCoreData<PerLogicalCore>
+{
+	type Item = (LogicalCoreIdentifier, &'a PerLogicalCore);
+
+	type IntoIter = PerLogicalCoreDataIterator<'a, PerLogicalCore>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter
+	{
+		self.iter()
+	}
+}
+
+impl<'a, PerLogicalCore: 'a> IntoIterator for &'a mut PerLogicalCoreData<PerLogicalCore>
+{
+	type Item = (LogicalCoreIdentifier, &'a mut PerLogicalCore);
+
+	type IntoIter = PerLogicalCoreDataMutableIterator<'a, PerLogicalCore>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter
+	{
+		self.iter_mut()
+	}
+}
+
+impl<PerLogicalCore> FromIterator<(LogicalCoreIdentifier, PerLogicalCore)> for PerLogicalCoreData<PerLogicalCore>
+{
+	/// If a logical core identifier occurs more than once, the last value for it is retained.
+	///
+	/// Panics if `iterator` is empty, as there must be at least one logical core.
+	#[inline(always)]
+	fn from_iter<I: IntoIterator<Item=(LogicalCoreIdentifier, PerLogicalCore)>>(iterator: I) -> Self
+	{
+		let mut this = Self
+		{
+			logical_cores_data: Vec::new().into_boxed_slice(),
+		};
+		this.extend(iterator);
+
+		assert_ne!(this.logical_cores_data.len(), 0, "Must be at least one logical core");
+		this
+	}
+}
+
+impl<PerLogicalCore> Extend<(LogicalCoreIdentifier, PerLogicalCore)> for PerLogicalCoreData<PerLogicalCore>
+{
+	/// Values for logical cores which already have data replace (and drop) that data.
+	///
+	/// Grows to accommodate logical core identifiers greater than any currently present.
+	#[inline(always)]
+	fn extend<I: IntoIterator<Item=(LogicalCoreIdentifier, PerLogicalCore)>>(&mut self, iterator: I)
 	{
-		let number_of_logical_cores = logical_cores.len();
-		assert_ne!(number_of_logical_cores, 0, "Must be at least one logical core");
+		let mut logical_cores_data = replace(&mut self.logical_cores_data, Vec::new().into_boxed_slice()).into_vec();
 
-		let mut logical_cores_data = Vec::with_capacity(number_of_logical_cores);
-		for _logical_core_index in 0 .. number_of_logical_cores
+		for (logical_core_identifier, value) in iterator
 		{
-			logical_cores_data.push(None);
+			let logical_core_index = logical_core_identifier as usize;
+			if logical_core_index >= logical_cores_data.len()
+			{
+				logical_cores_data.resize_with(logical_core_index + 1, || None);
+			}
+			logical_cores_data[logical_core_index] = Some(value);
 		}
 
-		Self
+		self.logical_cores_data = logical_cores_data.into_boxed_slice();
+	}
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl<PerLogicalCore> PerLogicalCoreData<NumaLocalBox<PerLogicalCore>>
+{
+	/// `constructor` is called for each defined logical core in `logical_cores`; it is passed the logical core's identifier.
+	///
+	/// The value it returns is moved into memory on the NUMA node of that logical core (see `NumaLocalBox::new_for_logical_core()`).
+	#[inline(always)]
+	pub fn new_numa_local(logical_cores: &LogicalCores, mut constructor: impl FnMut(LogicalCoreIdentifier) -> PerLogicalCore) -> io::Result<Self>
+	{
+		let mut numa_local_boxes = Vec::with_capacity(logical_cores.len());
+		for logical_core_identifier_reference in logical_cores.iter()
 		{
-			logical_cores_data: logical_cores_data.into_boxed_slice()
+			let logical_core_identifier = *logical_core_identifier_reference;
+			numa_local_boxes.push((logical_core_identifier, NumaLocalBox::new_for_logical_core(constructor(logical_core_identifier), logical_core_identifier)?));
 		}
+		Ok(Self::from_iter(numa_local_boxes))
+	}
+}
+
+impl<PerLogicalCore> PerLogicalCoreData<PerLogicalCore>
+{
+	/// Creates an empty set of logical core data.
+	///
+	/// Data can then be set for any of `logical_cores`, which need not be contiguous.
+	#[inline(always)]
+	pub fn empty(logical_cores: &LogicalCores) -> Self
+	{
+		Self::new_internal(logical_cores, |_logical_core_identifier| None)
 	}
 
 	/// `constructor` is called for each defined logical core in `logical_cores`; it is passed the logical core's identifier.
@@ -77,6 +168,60 @@ impl<PerLogicalCore> PerLogicalCoreData<PerLogicalCore>
 		Self::new_internal(logical_cores, |logical_core_identifier| Some(constructor(logical_core_identifier)))
 	}
 
+	/// `constructor` is called for each defined logical core in `logical_cores` on a short-lived thread whose affinity has been set to just that logical core; it is passed the logical core's identifier.
+	///
+	/// All the threads run in parallel and are joined before this method returns.
+	///
+	/// Memory first touched by `constructor` is therefore allocated by the Operating System on the NUMA node of the logical core that owns it (assuming the default 'local' memory policy is in use).
+	///
+	/// Fails if a thread can not be spawned or its affinity can not be set; if `constructor` panics, the panic is propagated to the caller.
+	///
+	/// On platforms which do not support setting thread affinity, `constructor` still runs on a separate thread, but that thread is not pinned.
+	#[inline(always)]
+	pub fn new_on_each_core(logical_cores: &LogicalCores, constructor: impl Fn(LogicalCoreIdentifier) -> PerLogicalCore + Sync) -> io::Result<Self>
+	where PerLogicalCore: Send
+	{
+		let constructor = &constructor;
+
+		let constructed = scope(|scope| -> io::Result<Vec<(LogicalCoreIdentifier, PerLogicalCore)>>
+		{
+			let mut join_handles = Vec::with_capacity(logical_cores.len());
+			for logical_core_identifier_reference in logical_cores.iter()
+			{
+				let logical_core_identifier = *logical_core_identifier_reference;
+
+				let join_handle = Builder::new().name(format!("logical-core-{}", logical_core_identifier)).spawn_scoped(scope, move || -> io::Result<PerLogicalCore>
+				{
+					LogicalCores::set_current_thread_affinity_for_only_logical_core(logical_core_identifier)?;
+					Ok(constructor(logical_core_identifier))
+				})?;
+				join_handles.push((logical_core_identifier, join_handle));
+			}
+
+			let mut constructed = Vec::with_capacity(join_handles.len());
+			for (logical_core_identifier, join_handle) in join_handles
+			{
+				match join_handle.join()
+				{
+					Ok(result) => constructed.push((logical_core_identifier, result?)),
+					Err(panic) => resume_unwind(panic),
+				}
+			}
+			Ok(constructed)
+		})?;
+
+		let mut constructed = constructed.into_iter();
+		Ok
+		(
+			Self::new_internal(logical_cores, |logical_core_identifier|
+			{
+				let (constructed_for_logical_core_identifier, value) = constructed.next().unwrap();
+				debug_assert_eq!(constructed_for_logical_core_identifier, logical_core_identifier);
+				Some(value)
+			})
+		)
+	}
+
 	#[inline(always)]
 	fn new_internal(logical_cores: &LogicalCores, mut constructor: impl FnMut(LogicalCoreIdentifier) -> Option<PerLogicalCore>) -> Self
 	{
@@ -111,6 +256,63 @@ impl<PerLogicalCore> PerLogicalCoreData<PerLogicalCore>
 		}
 	}
 
+	/// Grows or shrinks to match `logical_cores`, eg after a cgroup cpuset change or a `LogicalCoreHotplugEvent`.
+	///
+	/// `constructor` is called for each logical core in `logical_cores` without data; it is passed the logical core's identifier.
+	/// Data for logical cores not in `logical_cores` is removed and returned, in ascending order of logical core identifier, so that it can be drained.
+	/// Data for logical cores in both is untouched, and remains at the same index.
+	///
+	/// If `constructor` panics, no data is removed, although data may have been constructed for some logical cores.
+	#[inline(always)]
+	pub fn resize(&mut self, logical_cores: &LogicalCores, mut constructor: impl FnMut(LogicalCoreIdentifier) -> PerLogicalCore) -> Vec<(LogicalCoreIdentifier, PerLogicalCore)>
+	{
+		assert_ne!(logical_cores.len(), 0, "Must be at least one logical core");
+
+		let number_of_logical_cores = (*logical_cores.iter().next_back().unwrap() as usize) + 1;
+		if number_of_logical_cores > self.logical_cores_data.len()
+		{
+			self.resize_logical_cores_data(number_of_logical_cores);
+		}
+
+		for logical_core_identifier_reference in logical_cores.iter()
+		{
+			let logical_core_identifier = *logical_core_identifier_reference;
+			if self.logical_cores_data[logical_core_identifier as usize].is_none()
+			{
+				self.logical_cores_data[logical_core_identifier as usize] = Some(constructor(logical_core_identifier))
+			}
+		}
+
+		let mut removed = Vec::new();
+		for (logical_core_index, logical_core_data) in self.logical_cores_data.iter_mut().enumerate()
+		{
+			let logical_core_identifier = logical_core_index as LogicalCoreIdentifier;
+			if !logical_cores.contains(&logical_core_identifier)
+			{
+				if let Some(value) = logical_core_data.take()
+				{
+					removed.push((logical_core_identifier, value))
+				}
+			}
+		}
+
+		if number_of_logical_cores < self.logical_cores_data.len()
+		{
+			self.resize_logical_cores_data(number_of_logical_cores);
+		}
+
+		removed
+	}
+
+	/// Grows with `None` or truncates; never panics, so data is never lost.
+	#[inline(always)]
+	fn resize_logical_cores_data(&mut self, number_of_logical_cores: usize)
+	{
+		let mut logical_cores_data = replace(&mut self.logical_cores_data, Vec::new().into_boxed_slice()).into_vec();
+		logical_cores_data.resize_with(number_of_logical_cores, || None);
+		self.logical_cores_data = logical_cores_data.into_boxed_slice();
+	}
+
 	/// Gets the data for a particular logical core.
 	///
 	/// If the logical core does not exist (or does not have assigned data), returns None; this can happen on Linux if using the SO_INCOMING_CPU socket option, which can map to a CPU not assigned to the process.
@@ -135,6 +337,57 @@ impl<PerLogicalCore> PerLogicalCoreData<PerLogicalCore>
 		self.get_or(logical_core_identifier, LogicalCores::current_logical_core)
 	}
 
+	/// Gets the data for the logical core which processed incoming packets for a socket (`SO_INCOMING_CPU`), eg to route an accepted connection to the worker which owns that logical core.
+	///
+	/// If that logical core does not have assigned data (eg because it is not available to the process), falls back to the nearest logical core which does in the same NUMA node, as found by `Topology::nearest_logical_core()`; only if there is none in the same NUMA node is the nearest logical core in any NUMA node used.
+	/// If no incoming packets have yet been processed for the socket, the fallback is from `LogicalCores::current_logical_core()`.
+	/// For this to work well, `topology` should include logical cores not available to the process, eg by discovering it from `LogicalCores::online()`.
+	///
+	/// Returns the logical core used as well as its data.
+	///
+	/// Fails if `SO_INCOMING_CPU` can not be read, or if no logical core has assigned data (`NotFound`).
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn dispatch_to_owner(&self, socket_file_descriptor: RawFd, topology: &Topology) -> io::Result<(LogicalCoreIdentifier, &PerLogicalCore)>
+	{
+		let logical_core_identifier = self.owner(Self::incoming_logical_core_or_current(socket_file_descriptor)?, topology)?;
+		Ok((logical_core_identifier, self.get(logical_core_identifier).unwrap()))
+	}
+
+	/// As `dispatch_to_owner()`, but gets the mutable data.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn dispatch_to_owner_mut(&mut self, socket_file_descriptor: RawFd, topology: &Topology) -> io::Result<(LogicalCoreIdentifier, &mut PerLogicalCore)>
+	{
+		let logical_core_identifier = self.owner(Self::incoming_logical_core_or_current(socket_file_descriptor)?, topology)?;
+		Ok((logical_core_identifier, self.get_mut(logical_core_identifier).unwrap()))
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	fn incoming_logical_core_or_current(socket_file_descriptor: RawFd) -> io::Result<LogicalCoreIdentifier>
+	{
+		match LogicalCores::incoming_logical_core_for_socket(socket_file_descriptor)
+		{
+			Err(ref error) if error.kind() == io::ErrorKind::NotFound => Ok(LogicalCores::current_logical_core()),
+			result => result,
+		}
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	fn owner(&self, incoming_logical_core_identifier: LogicalCoreIdentifier, topology: &Topology) -> io::Result<LogicalCoreIdentifier>
+	{
+		if likely!(self.get(incoming_logical_core_identifier).is_some())
+		{
+			return Ok(incoming_logical_core_identifier)
+		}
+
+		let numa_node = topology.logical_core_topology(incoming_logical_core_identifier).numa_node;
+		let in_same_numa_node = self.logical_core_indices().filter(|&logical_core_identifier| topology.logical_core_topology(logical_core_identifier).numa_node == numa_node);
+		topology.nearest_logical_core(incoming_logical_core_identifier, in_same_numa_node).or_else(|| topology.nearest_logical_core(incoming_logical_core_identifier, self.logical_core_indices())).ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No logical cores have assigned data"))
+	}
+
 	/// Gets the data for a particular logical core; if no data for that core, gets it for the `default_logical_core_identifier`.
 	///
 	/// If the logical core does not exist (or does not have assigned data), returns None; this can happen on Linux if using the` SO_INCOMING_CPU` socket option, which can return an index for a CPU not assigned to the process.
@@ -222,7 +475,21 @@ impl<PerLogicalCore> PerLogicalCoreData<PerLogicalCore>
 	#[inline(always)]
 	pub fn logical_core_indices<'a>(&'a self) -> impl Iterator<Item=LogicalCoreIdentifier> + 'a
 	{
-		(0u16 .. self.logical_cores_data.len() as LogicalCoreIdentifier).into_iter().filter(move |potential_logical_core_index| self.get(*potential_logical_core_index).is_some())
+		self.iter().map(|(logical_core_identifier, _)| logical_core_identifier)
+	}
+
+	/// Iterates over all entries that are not `None`, yielding the logical core identifier and a reference to its data.
+	#[inline(always)]
+	pub fn iter<'a>(&'a self) -> PerLogicalCoreDataIterator<'a, PerLogicalCore>
+	{
+		PerLogicalCoreDataIterator(self.logical_cores_data.iter().enumerate())
+	}
+
+	/// Iterates over all entries that are not `None`, yielding the logical core identifier and a mutable reference to its data.
+	#[inline(always)]
+	pub fn iter_mut<'a>(&'a mut self) -> PerLogicalCoreDataMutableIterator<'a, PerLogicalCore>
+	{
+		PerLogicalCoreDataMutableIterator(self.logical_cores_data.iter_mut().enumerate())
 	}
 
 	/// Maps from `T` to `V` assuming that entries with `Some()` in them are mappable.
@@ -237,11 +504,7 @@ impl<PerLogicalCore> PerLogicalCoreData<PerLogicalCore>
 
 		for logical_core_index in 0 .. number_of_logical_cores as LogicalCoreIdentifier
 		{
-			let v_option = match self.take(logical_core_index)
-			{
-				None => None,
-				Some(t) => Some(mapper(logical_core_index as LogicalCoreIdentifier, t)),
-			};
+			let v_option = self.take(logical_core_index).map(|t| mapper(logical_core_index, t));
 			mapped_logical_cores_data.push(v_option);
 		}
 
@@ -251,3 +514,241 @@ impl<PerLogicalCore> PerLogicalCoreData<PerLogicalCore>
 		}
 	}
 }
+
+#[cfg(test)]
+mod per_logical_core_data_tests
+{
+	use super::*;
+	use ::std::panic::AssertUnwindSafe;
+	use ::std::panic::catch_unwind;
+
+	#[test]
+	fn empty_can_be_set_for_sparse_logical_cores()
+	{
+		let mut per_logical_core_data = PerLogicalCoreData::empty(&logical_cores(&[0, 2, 5]));
+		assert_eq!(per_logical_core_data.logical_core_indices().count(), 0);
+
+		per_logical_core_data.set(5, "five");
+		per_logical_core_data.set(2, "two");
+		assert_eq!(per_logical_core_data.iter().collect::<Vec<_>>(), vec![(2, &"two"), (5, &"five")]);
+	}
+
+	#[test]
+	fn resize_grows_and_preserves_indices()
+	{
+		let mut per_logical_core_data = PerLogicalCoreData::new(&logical_cores(&[0, 2]), |logical_core_identifier| logical_core_identifier * 10);
+
+		let removed = per_logical_core_data.resize(&logical_cores(&[0, 2, 3, 7]), |logical_core_identifier| logical_core_identifier * 100);
+
+		assert_eq!(removed, Vec::new());
+		assert_eq!(per_logical_core_data.len(), 8);
+		assert_eq!(per_logical_core_data.iter().map(|(logical_core_identifier, value)| (logical_core_identifier, *value)).collect::<Vec<_>>(), vec![(0, 0), (2, 20), (3, 300), (7, 700)]);
+	}
+
+	#[test]
+	fn resize_shrinks_and_returns_removed()
+	{
+		let mut per_logical_core_data = PerLogicalCoreData::new(&logical_cores(&[0, 2, 5]), |logical_core_identifier| logical_core_identifier * 10);
+
+		let removed = per_logical_core_data.resize(&logical_cores(&[1, 2]), |logical_core_identifier| logical_core_identifier * 100);
+
+		assert_eq!(removed, vec![(0, 0), (5, 50)]);
+		assert_eq!(per_logical_core_data.len(), 3);
+		assert_eq!(per_logical_core_data.get(0), None);
+		assert_eq!(per_logical_core_data.get(1), Some(&100));
+		assert_eq!(per_logical_core_data.get(2), Some(&20));
+	}
+
+	#[test]
+	fn resize_keeps_data_if_constructor_panics()
+	{
+		let mut per_logical_core_data = PerLogicalCoreData::new(&logical_cores(&[0, 2, 5]), |logical_core_identifier| logical_core_identifier * 10);
+
+		let result = catch_unwind(AssertUnwindSafe(|| per_logical_core_data.resize(&logical_cores(&[2, 9]), |_| panic!("constructor"))));
+
+		assert!(result.is_err());
+		assert_eq!(per_logical_core_data.iter().map(|(logical_core_identifier, value)| (logical_core_identifier, *value)).collect::<Vec<_>>(), vec![(0, 0), (2, 20), (5, 50)]);
+		assert_eq!(per_logical_core_data.get_or(2, || 0), &20);
+	}
+
+	#[test]
+	fn iter_skips_logical_cores_without_data_in_both_directions()
+	{
+		let per_logical_core_data = PerLogicalCoreData::new(&logical_cores(&[1, 3, 4]), |logical_core_identifier| logical_core_identifier * 10);
+
+		assert_eq!(per_logical_core_data.iter().map(|(logical_core_identifier, value)| (logical_core_identifier, *value)).collect::<Vec<_>>(), vec![(1, 10), (3, 30), (4, 40)]);
+		assert_eq!(per_logical_core_data.iter().rev().map(|(logical_core_identifier, value)| (logical_core_identifier, *value)).collect::<Vec<_>>(), vec![(4, 40), (3, 30), (1, 10)]);
+
+		let mut iterator = per_logical_core_data.iter();
+		assert_eq!(iterator.next(), Some((1, &10)));
+		assert_eq!(iterator.next_back(), Some((4, &40)));
+		assert_eq!(iterator.next(), Some((3, &30)));
+		assert_eq!(iterator.next_back(), None);
+		assert_eq!(iterator.next(), None);
+	}
+
+	#[test]
+	fn iter_mut_modifies_in_place()
+	{
+		let mut per_logical_core_data = PerLogicalCoreData::new(&logical_cores(&[0, 2]), |logical_core_identifier| logical_core_identifier);
+
+		for (logical_core_identifier, value) in per_logical_core_data.iter_mut()
+		{
+			*value += logical_core_identifier + 100;
+		}
+		for (_, value) in &mut per_logical_core_data
+		{
+			*value += 1;
+		}
+
+		assert_eq!(per_logical_core_data.get(0), Some(&101));
+		assert_eq!(per_logical_core_data.get(1), None);
+		assert_eq!(per_logical_core_data.get(2), Some(&105));
+		assert_eq!(per_logical_core_data.iter_mut().rev().map(|(logical_core_identifier, _)| logical_core_identifier).collect::<Vec<_>>(), vec![2, 0]);
+	}
+
+	#[test]
+	fn into_iter_yields_owned_values()
+	{
+		let per_logical_core_data = PerLogicalCoreData::new(&logical_cores(&[2, 5]), |logical_core_identifier| format!("core {}", logical_core_identifier));
+
+		assert_eq!((&per_logical_core_data).into_iter().count(), 2);
+		assert_eq!(per_logical_core_data.clone().into_iter().rev().collect::<Vec<_>>(), vec![(5, "core 5".to_string()), (2, "core 2".to_string())]);
+		assert_eq!(per_logical_core_data.into_iter().collect::<Vec<_>>(), vec![(2, "core 2".to_string()), (5, "core 5".to_string())]);
+	}
+
+	#[test]
+	fn from_iter_sizes_to_highest_logical_core_and_keeps_last_value()
+	{
+		let per_logical_core_data = vec![(3, 'a'), (1, 'b'), (3, 'c')].into_iter().collect::<PerLogicalCoreData<_>>();
+
+		assert_eq!(per_logical_core_data.len(), 4);
+		assert_eq!(per_logical_core_data.iter().map(|(logical_core_identifier, value)| (logical_core_identifier, *value)).collect::<Vec<_>>(), vec![(1, 'b'), (3, 'c')]);
+	}
+
+	#[test]
+	#[should_panic(expected = "Must be at least one logical core")]
+	fn from_iter_panics_if_empty()
+	{
+		let _ = Vec::<(LogicalCoreIdentifier, u8)>::new().into_iter().collect::<PerLogicalCoreData<_>>();
+	}
+
+	#[test]
+	fn extend_grows_and_replaces()
+	{
+		let mut per_logical_core_data = PerLogicalCoreData::new(&logical_cores(&[0, 1]), |logical_core_identifier| logical_core_identifier);
+
+		per_logical_core_data.extend(vec![(1, 10), (6, 60)]);
+
+		assert_eq!(per_logical_core_data.len(), 7);
+		assert_eq!(per_logical_core_data.iter().map(|(logical_core_identifier, value)| (logical_core_identifier, *value)).collect::<Vec<_>>(), vec![(0, 0), (1, 10), (6, 60)]);
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn new_on_each_core_constructs_on_that_logical_core()
+	{
+		let logical_cores = LogicalCores::valid_logical_cores_for_the_current_process();
+
+		let per_logical_core_data = PerLogicalCoreData::new_on_each_core(&logical_cores, |logical_core_identifier| (logical_core_identifier, LogicalCores::current_logical_core(), ::std::thread::current().name().map(|name| name.to_string()))).unwrap();
+
+		assert_eq!(per_logical_core_data.logical_core_indices().collect::<BTreeSet<_>>(), logical_cores.iter().cloned().collect::<BTreeSet<_>>());
+		for (logical_core_identifier, &(passed_logical_core_identifier, ran_on_logical_core, ref thread_name)) in per_logical_core_data.iter()
+		{
+			assert_eq!(passed_logical_core_identifier, logical_core_identifier);
+			assert_eq!(ran_on_logical_core, logical_core_identifier);
+			assert_eq!(thread_name.as_ref().map(String::as_str), Some(format!("logical-core-{}", logical_core_identifier).as_str()));
+		}
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn new_on_each_core_propagates_panic()
+	{
+		let logical_cores = LogicalCores::valid_logical_cores_for_the_current_process();
+
+		let result = catch_unwind(|| PerLogicalCoreData::new_on_each_core(&logical_cores, |_| -> u8 { panic!("constructor") }));
+
+		let panic = result.unwrap_err();
+		assert_eq!(panic.downcast_ref::<&str>(), Some(&"constructor"));
+	}
+
+	/// Sub-NUMA clustering: logical cores 1 and 3 share a last level cache, but are in different NUMA nodes.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	fn sub_numa_clustered_topology() -> Topology
+	{
+		let logical_core_topology = |numa_node, last_level_cache, physical_core| LogicalCoreTopology { package: 0, numa_node, last_level_cache, physical_core };
+		vec![(0, logical_core_topology(0, 0, 0)), (1, logical_core_topology(0, 1, 1)), (2, logical_core_topology(1, 2, 2)), (3, logical_core_topology(1, 1, 3))].into_iter().collect::<PerLogicalCoreData<_>>().into()
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn owner_prefers_same_numa_node()
+	{
+		let topology = sub_numa_clustered_topology();
+		let per_logical_core_data = PerLogicalCoreData::new(&logical_cores(&[1, 2]), |logical_core_identifier| logical_core_identifier);
+
+		assert_eq!(per_logical_core_data.owner(1, &topology).unwrap(), 1);
+		assert_eq!(per_logical_core_data.owner(3, &topology).unwrap(), 2);
+		assert_eq!(per_logical_core_data.owner(0, &topology).unwrap(), 1);
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn owner_widens_to_other_numa_nodes()
+	{
+		let topology = sub_numa_clustered_topology();
+		let per_logical_core_data = PerLogicalCoreData::new(&logical_cores(&[0, 1]), |logical_core_identifier| logical_core_identifier);
+
+		assert_eq!(per_logical_core_data.owner(3, &topology).unwrap(), 1);
+		assert_eq!(per_logical_core_data.owner(2, &topology).unwrap(), 1);
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn dispatch_to_owner_uses_incoming_logical_core_of_loopback_connection()
+	{
+		use ::std::io::Read;
+		use ::std::io::Write;
+		use ::std::net::TcpListener;
+		use ::std::net::TcpStream;
+		use ::std::os::unix::io::AsRawFd;
+
+		let current_logical_core = LogicalCores::current_logical_core();
+		LogicalCores::set_current_thread_affinity_for_only_logical_core(current_logical_core).unwrap();
+
+		let logical_cores = LogicalCores::valid_logical_cores_for_the_current_process();
+		let topology = Topology::discover(&logical_cores);
+		let mut per_logical_core_data = PerLogicalCoreData::new(&logical_cores, |logical_core_identifier| logical_core_identifier);
+
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+		let (mut server, _) = listener.accept().unwrap();
+		client.write_all(b"x").unwrap();
+		let mut buffer = [0u8; 1];
+		server.read_exact(&mut buffer).unwrap();
+
+		assert_eq!(per_logical_core_data.dispatch_to_owner(server.as_raw_fd(), &topology).unwrap(), (current_logical_core, &current_logical_core));
+		assert_eq!(per_logical_core_data.dispatch_to_owner_mut(server.as_raw_fd(), &topology).unwrap().0, current_logical_core);
+	}
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[test]
+	fn dispatch_to_owner_falls_back_if_no_packets_processed()
+	{
+		use ::std::net::UdpSocket;
+		use ::std::os::unix::io::AsRawFd;
+
+		let current_logical_core = LogicalCores::current_logical_core();
+		LogicalCores::set_current_thread_affinity_for_only_logical_core(current_logical_core).unwrap();
+
+		let logical_cores = LogicalCores::valid_logical_cores_for_the_current_process();
+		let topology = Topology::discover(&logical_cores);
+		let per_logical_core_data = PerLogicalCoreData::new(&logical_cores, |logical_core_identifier| logical_core_identifier);
+
+		let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+		assert_eq!(LogicalCores::incoming_logical_core_for_socket(socket.as_raw_fd()).unwrap_err().kind(), io::ErrorKind::NotFound);
+		assert_eq!(per_logical_core_data.dispatch_to_owner(socket.as_raw_fd(), &topology).unwrap().0, current_logical_core);
+	}
+}
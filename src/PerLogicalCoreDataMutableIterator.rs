@@ -0,0 +1,51 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Mutably iterates over the entries of a `PerLogicalCoreData` that are not `None`, in ascending order of logical core identifier.
+#[derive(Debug)]
+pub struct PerLogicalCoreDataMutableIterator<'a, PerLogicalCore: 'a>(Enumerate<slice::IterMut<'a, Option<PerLogicalCore>>>);
+
+impl<'a, PerLogicalCore: 'a> Iterator for PerLogicalCoreDataMutableIterator<'a, PerLogicalCore>
+{
+	type Item = (LogicalCoreIdentifier, &'a mut PerLogicalCore);
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		for (logical_core_index, per_logical_core) in self.0.by_ref()
+		{
+			if let Some(per_logical_core) = per_logical_core.as_mut()
+			{
+				return Some((logical_core_index as LogicalCoreIdentifier, per_logical_core))
+			}
+		}
+		None
+	}
+
+	#[inline(always)]
+	fn size_hint(&self) -> (usize, Option<usize>)
+	{
+		(0, self.0.size_hint().1)
+	}
+}
+
+impl<'a, PerLogicalCore: 'a> DoubleEndedIterator for PerLogicalCoreDataMutableIterator<'a, PerLogicalCore>
+{
+	#[inline(always)]
+	fn next_back(&mut self) -> Option<Self::Item>
+	{
+		for (logical_core_index, per_logical_core) in self.0.by_ref().rev()
+		{
+			if let Some(per_logical_core) = per_logical_core.as_mut()
+			{
+				return Some((logical_core_index as LogicalCoreIdentifier, per_logical_core))
+			}
+		}
+		None
+	}
+}
+
+impl<'a, PerLogicalCore: 'a> FusedIterator for PerLogicalCoreDataMutableIterator<'a, PerLogicalCore>
+{
+}
@@ -0,0 +1,8 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Physical core identifier.
+///
+/// This is the underlying hardware's identifier, as found in `/sys/devices/system/cpu/cpu<N>/topology/core_id`; it is only unique within a package.
+pub type PhysicalCoreIdentifier = u16;
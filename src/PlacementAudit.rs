@@ -0,0 +1,190 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Compares the desired placement of threads in a process with their actual allowed logical cores and the logical cores they have been observed running on.
+///
+/// Intended to be run periodically, eg from a monitoring thread, with any drift being alerted on.
+/// Each `audit()` observes the logical core each thread last ran on; observations accumulate, so a thread observed outside its placement continues to be reported until `forget_observations()` is called.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacementAudit
+{
+	process_identifier: ProcessIdentifier,
+	desired: Vec<(ThreadSelector, LogicalCores)>,
+	observed: BTreeMap<TaskIdentifier, BTreeSet<LogicalCoreIdentifier>>,
+}
+
+impl PlacementAudit
+{
+	/// Creates a new instance for a process, with no desired placements.
+	#[inline(always)]
+	pub fn new(process_identifier: ProcessIdentifier) -> Self
+	{
+		Self
+		{
+			process_identifier,
+			desired: Vec::new(),
+			observed: BTreeMap::new(),
+		}
+	}
+
+	/// Creates a new instance for the current process, with no desired placements.
+	#[inline(always)]
+	pub fn for_current_process() -> Self
+	{
+		Self::new(process::id() as ProcessIdentifier)
+	}
+
+	/// Adds a desired placement.
+	#[inline(always)]
+	pub fn desire(&mut self, thread_selector: ThreadSelector, logical_cores: LogicalCores)
+	{
+		self.desired.push((thread_selector, logical_cores))
+	}
+
+	/// Inspects the threads of the process, records the logical core each last ran on and reports drift from the desired placements.
+	///
+	/// Slow as it uses `ProcessThreads::inspect()`.
+	#[inline(always)]
+	pub fn audit(&mut self) -> io::Result<Vec<PlacementDrift>>
+	{
+		let process_threads = ProcessThreads::inspect(self.process_identifier)?;
+		Ok(self.audit_threads(&process_threads))
+	}
+
+	/// As `audit()`, but for threads already inspected, eg by `ProcessThreads::inspect()`.
+	pub fn audit_threads(&mut self, process_threads: &ProcessThreads) -> Vec<PlacementDrift>
+	{
+		use self::PlacementDrift::*;
+
+		self.observed.retain(|task_identifier, _| process_threads.iter().any(|process_thread| process_thread.task_identifier == *task_identifier));
+		for process_thread in process_threads.iter()
+		{
+			self.observed.entry(process_thread.task_identifier).or_default().insert(process_thread.last_logical_core);
+		}
+
+		let mut drift = Vec::new();
+		for (thread_selector, desired) in self.desired.iter()
+		{
+			let mut is_missing = true;
+			for process_thread in process_threads.iter().filter(|process_thread| thread_selector.selects(process_thread))
+			{
+				is_missing = false;
+				let task_identifier = process_thread.task_identifier;
+
+				if process_thread.allowed_logical_cores != *desired
+				{
+					drift.push(AffinityChanged { thread_selector: thread_selector.clone(), task_identifier, desired: desired.clone(), actual: process_thread.allowed_logical_cores.clone() });
+				}
+
+				let observed: BTreeSet<LogicalCoreIdentifier> = self.observed[&task_identifier].difference(desired).cloned().collect();
+				if !observed.is_empty()
+				{
+					drift.push(RanOutsidePlacement { thread_selector: thread_selector.clone(), task_identifier, desired: desired.clone(), observed: LogicalCores::from(observed) });
+				}
+			}
+
+			if is_missing
+			{
+				drift.push(Missing { thread_selector: thread_selector.clone() });
+			}
+		}
+		drift
+	}
+
+	/// Forgets the logical cores threads have been observed running on, eg once drift has been alerted on and corrected.
+	#[inline(always)]
+	pub fn forget_observations(&mut self)
+	{
+		self.observed.clear()
+	}
+}
+
+#[cfg(test)]
+mod placement_audit_tests
+{
+	use super::*;
+
+	fn process_thread(task_identifier: TaskIdentifier, name: &str, allowed_logical_cores: &[LogicalCoreIdentifier], last_logical_core: LogicalCoreIdentifier) -> ProcessThread
+	{
+		ProcessThread
+		{
+			task_identifier,
+			name: name.to_string(),
+			allowed_logical_cores: logical_cores(allowed_logical_cores),
+			last_logical_core,
+			scheduling_policy: SchedulingPolicy::default(),
+			voluntary_context_switches: 0,
+			involuntary_context_switches: 0,
+		}
+	}
+
+	fn placement_audit() -> PlacementAudit
+	{
+		let mut placement_audit = PlacementAudit::new(1);
+		placement_audit.desire(ThreadSelector::Name("worker".to_string()), logical_cores(&[2, 3]));
+		placement_audit.desire(ThreadSelector::TaskIdentifier(11), logical_cores(&[0]));
+		placement_audit
+	}
+
+	#[test]
+	fn no_drift()
+	{
+		let mut placement_audit = placement_audit();
+		let process_threads = ProcessThreads(vec![process_thread(10, "worker", &[2, 3], 2), process_thread(11, "main", &[0], 0), process_thread(12, "worker", &[2, 3], 3)]);
+
+		assert_eq!(placement_audit.audit_threads(&process_threads), vec![]);
+	}
+
+	#[test]
+	fn missing()
+	{
+		let mut placement_audit = placement_audit();
+		let process_threads = ProcessThreads(vec![process_thread(10, "other", &[2, 3], 2)]);
+
+		assert_eq!(placement_audit.audit_threads(&process_threads), vec![PlacementDrift::Missing { thread_selector: ThreadSelector::Name("worker".to_string()) }, PlacementDrift::Missing { thread_selector: ThreadSelector::TaskIdentifier(11) }]);
+	}
+
+	#[test]
+	fn affinity_changed()
+	{
+		let mut placement_audit = placement_audit();
+		let process_threads = ProcessThreads(vec![process_thread(10, "worker", &[2, 3], 2), process_thread(11, "main", &[0, 1], 0)]);
+
+		assert_eq!(placement_audit.audit_threads(&process_threads), vec![PlacementDrift::AffinityChanged { thread_selector: ThreadSelector::TaskIdentifier(11), task_identifier: 11, desired: logical_cores(&[0]), actual: logical_cores(&[0, 1]) }]);
+	}
+
+	#[test]
+	fn ran_outside_placement_accumulates_until_forgotten()
+	{
+		let mut placement_audit = placement_audit();
+		let ran_outside = ProcessThreads(vec![process_thread(10, "worker", &[2, 3], 1), process_thread(11, "main", &[0], 0)]);
+		let ran_inside = ProcessThreads(vec![process_thread(10, "worker", &[2, 3], 2), process_thread(11, "main", &[0], 0)]);
+		let drift = vec![PlacementDrift::RanOutsidePlacement { thread_selector: ThreadSelector::Name("worker".to_string()), task_identifier: 10, desired: logical_cores(&[2, 3]), observed: logical_cores(&[1]) }];
+
+		assert_eq!(placement_audit.audit_threads(&ran_outside), drift);
+		assert_eq!(placement_audit.audit_threads(&ran_inside), drift);
+
+		placement_audit.forget_observations();
+		assert_eq!(placement_audit.audit_threads(&ran_inside), vec![]);
+	}
+
+	#[test]
+	fn observations_of_exited_threads_are_discarded()
+	{
+		let mut placement_audit = placement_audit();
+		placement_audit.audit_threads(&ProcessThreads(vec![process_thread(10, "worker", &[2, 3], 1), process_thread(11, "main", &[0], 0)]));
+
+		let process_threads = ProcessThreads(vec![process_thread(11, "main", &[0], 0), process_thread(12, "worker", &[2, 3], 3)]);
+		assert_eq!(placement_audit.audit_threads(&process_threads), vec![]);
+		assert_eq!(placement_audit.observed.keys().cloned().collect::<Vec<_>>(), vec![11, 12]);
+	}
+
+	#[test]
+	fn name_is_truncated()
+	{
+		assert!(ThreadSelector::Name("a-long-thread-name".to_string()).selects(&process_thread(10, "a-long-thread-n", &[0], 0)));
+		assert!(ThreadSelector::Name("fourteen-bytes\u{e9}".to_string()).selects(&process_thread(10, "fourteen-bytes", &[0], 0)));
+		assert!(!ThreadSelector::Name("a-long-thread".to_string()).selects(&process_thread(10, "a-long-thread-n", &[0], 0)));
+	}
+}
@@ -0,0 +1,63 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A difference between the desired and actual placement of a thread, as found by a `PlacementAudit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlacementDrift
+{
+	/// No threads are selected, eg because the thread has exited or was never named.
+	Missing
+	{
+		/// Selector.
+		thread_selector: ThreadSelector,
+	},
+
+	/// The thread's allowed logical cores are not those desired, eg because they were reset by a container runtime or changed by an operator using `taskset`.
+	AffinityChanged
+	{
+		/// Selector.
+		thread_selector: ThreadSelector,
+
+		/// Linux task identifier (`tid`).
+		task_identifier: TaskIdentifier,
+
+		/// Desired logical cores.
+		desired: LogicalCores,
+
+		/// Actual allowed logical cores.
+		actual: LogicalCores,
+	},
+
+	/// The thread has been observed running on logical cores which are not desired, eg before its affinity was set or while it was changed.
+	RanOutsidePlacement
+	{
+		/// Selector.
+		thread_selector: ThreadSelector,
+
+		/// Linux task identifier (`tid`).
+		task_identifier: TaskIdentifier,
+
+		/// Desired logical cores.
+		desired: LogicalCores,
+
+		/// Logical cores the thread has been observed running on which are not desired.
+		observed: LogicalCores,
+	},
+}
+
+impl Display for PlacementDrift
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::PlacementDrift::*;
+
+		match *self
+		{
+			Missing { ref thread_selector } => write!(f, "No {} exists", thread_selector),
+			AffinityChanged { task_identifier, ref desired, ref actual, .. } => write!(f, "Thread {} is allowed logical cores '{}' rather than '{}'", task_identifier, actual.to_linux_list_string(), desired.to_linux_list_string()),
+			RanOutsidePlacement { task_identifier, ref desired, ref observed, .. } => write!(f, "Thread {} has run on logical cores '{}' outside of '{}'", task_identifier, observed.to_linux_list_string(), desired.to_linux_list_string()),
+		}
+	}
+}
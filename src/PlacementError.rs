@@ -0,0 +1,36 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// An error from a `PlacementPlanner`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PlacementError
+{
+	/// There were fewer logical cores (or, for `PlacementPolicy::OnePerPhysicalCore`, physical cores) available than workers.
+	NotEnoughCores
+	{
+		/// The number of workers.
+		required: usize,
+
+		/// The number of logical (or physical) cores that could be used.
+		available: usize,
+	},
+}
+
+impl Display for PlacementError
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::PlacementError::*;
+
+		match self
+		{
+			&NotEnoughCores { required, available } => write!(f, "Placement of {} workers is impossible as only {} cores are available", required, available),
+		}
+	}
+}
+
+impl error::Error for PlacementError
+{
+}
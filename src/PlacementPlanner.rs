@@ -0,0 +1,216 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Assigns workers to logical cores in a pool according to a `PlacementPolicy`, taking account of packages, NUMA nodes and simultaneous multi-threading (SMT) siblings.
+///
+/// A typical pool is `LogicalCores::valid_logical_cores_for_the_current_process()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacementPlanner
+{
+	pool: LogicalCores,
+	topology: Topology,
+}
+
+impl PlacementPlanner
+{
+	/// Creates a new instance.
+	#[inline(always)]
+	pub fn new(pool: LogicalCores, topology: Topology) -> Self
+	{
+		Self
+		{
+			pool,
+			topology,
+		}
+	}
+
+	/// Creates a new instance, discovering the topology of `pool`.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn discover(pool: LogicalCores) -> Self
+	{
+		let topology = Topology::discover(&pool);
+		Self::new(pool, topology)
+	}
+
+	/// The pool of logical cores that workers are assigned from.
+	#[inline(always)]
+	pub fn pool(&self) -> &LogicalCores
+	{
+		&self.pool
+	}
+
+	/// The topology of the pool.
+	#[inline(always)]
+	pub fn topology(&self) -> &Topology
+	{
+		&self.topology
+	}
+
+	/// Assigns `number_of_workers` workers to logical cores.
+	///
+	/// The logical core for worker `n` is at index `n` in the result.
+	#[inline(always)]
+	pub fn plan(&self, placement_policy: PlacementPolicy, number_of_workers: usize) -> Result<Vec<LogicalCoreIdentifier>, PlacementError>
+	{
+		use self::PlacementPolicy::*;
+
+		let mut ordered = match placement_policy
+		{
+			Compact => self.compact_order(),
+			Scatter => self.scatter_order(),
+			OnePerPhysicalCore => self.one_per_physical_core_order(),
+			FillSmtLast => self.fill_smt_last_order(self.physical_cores()),
+		};
+
+		let available = ordered.len();
+		if unlikely!(number_of_workers > available)
+		{
+			return Err(PlacementError::NotEnoughCores { required: number_of_workers, available })
+		}
+
+		ordered.truncate(number_of_workers);
+		Ok(ordered)
+	}
+
+	/// As `plan()`, but returns the set of logical cores used rather than an assignment, eg to use with `LogicalCores::populate_per_logical_core_data()`.
+	#[inline(always)]
+	pub fn plan_logical_cores(&self, placement_policy: PlacementPolicy, number_of_workers: usize) -> Result<LogicalCores, PlacementError>
+	{
+		let assignment = self.plan(placement_policy, number_of_workers)?;
+		Ok(LogicalCores::from(assignment.into_iter().collect::<BTreeSet<_>>()))
+	}
+
+	#[inline(always)]
+	fn compact_order(&self) -> Vec<LogicalCoreIdentifier>
+	{
+		let mut ordered = Vec::with_capacity(self.pool.len());
+		for smt_siblings in self.physical_cores()
+		{
+			ordered.extend(smt_siblings);
+		}
+		ordered
+	}
+
+	#[inline(always)]
+	fn scatter_order(&self) -> Vec<LogicalCoreIdentifier>
+	{
+		let mut physical_cores_by_package: BTreeMap<PackageIdentifier, Vec<Vec<LogicalCoreIdentifier>>> = BTreeMap::new();
+		for (logical_core_topology, smt_siblings) in self.topology.physical_cores(&self.pool)
+		{
+			physical_cores_by_package.entry(logical_core_topology.package).or_default().push(smt_siblings.iter().cloned().collect());
+		}
+
+		let mut per_package_orders: Vec<_> = physical_cores_by_package.into_values().map(|physical_cores| self.fill_smt_last_order(physical_cores).into_iter()).collect();
+
+		let mut ordered = Vec::with_capacity(self.pool.len());
+		while ordered.len() != self.pool.len()
+		{
+			for per_package_order in per_package_orders.iter_mut()
+			{
+				if let Some(logical_core_identifier) = per_package_order.next()
+				{
+					ordered.push(logical_core_identifier)
+				}
+			}
+		}
+		ordered
+	}
+
+	#[inline(always)]
+	fn one_per_physical_core_order(&self) -> Vec<LogicalCoreIdentifier>
+	{
+		self.physical_cores().into_iter().map(|smt_siblings| smt_siblings[0]).collect()
+	}
+
+	#[inline(always)]
+	fn fill_smt_last_order(&self, physical_cores: Vec<Vec<LogicalCoreIdentifier>>) -> Vec<LogicalCoreIdentifier>
+	{
+		let maximum_smt_siblings = physical_cores.iter().map(|smt_siblings| smt_siblings.len()).max().unwrap_or(0);
+
+		let mut ordered = Vec::with_capacity(self.pool.len());
+		for smt_sibling_index in 0 .. maximum_smt_siblings
+		{
+			for smt_siblings in physical_cores.iter()
+			{
+				if let Some(logical_core_identifier) = smt_siblings.get(smt_sibling_index)
+				{
+					ordered.push(*logical_core_identifier)
+				}
+			}
+		}
+		ordered
+	}
+
+	/// Physical cores in topological order; the simultaneous multi-threading (SMT) siblings of each are in ascending order and never empty.
+	#[inline(always)]
+	fn physical_cores(&self) -> Vec<Vec<LogicalCoreIdentifier>>
+	{
+		self.topology.physical_cores(&self.pool).into_values().map(|smt_siblings| smt_siblings.iter().cloned().collect()).collect()
+	}
+}
+
+
+#[cfg(test)]
+mod placement_planner_tests
+{
+	use super::*;
+	use self::PlacementPolicy::*;
+
+	/// Two packages, each of two physical cores with two simultaneous multi-threading (SMT) siblings; as on Linux, the second SMT sibling of each physical core is numbered after all the first ones.
+	fn placement_planner() -> PlacementPlanner
+	{
+		let pool = logical_cores(&[0, 1, 2, 3, 4, 5, 6, 7]);
+		let topology = Topology::from(PerLogicalCoreData::new(&pool, |logical_core_identifier|
+		{
+			let physical_core = logical_core_identifier % 4;
+			let package = (physical_core / 2) as PackageIdentifier;
+			LogicalCoreTopology
+			{
+				package,
+				numa_node: package as NumaNodeIdentifier,
+				last_level_cache: physical_core / 2 * 2,
+				physical_core,
+			}
+		}));
+		PlacementPlanner::new(pool, topology)
+	}
+
+	#[test]
+	fn compact_uses_smt_siblings_together()
+	{
+		assert_eq!(placement_planner().plan(Compact, 8), Ok(vec![0, 4, 1, 5, 2, 6, 3, 7]));
+		assert_eq!(placement_planner().plan(Compact, 3), Ok(vec![0, 4, 1]));
+	}
+
+	#[test]
+	fn scatter_alternates_packages_and_fills_smt_siblings_last()
+	{
+		assert_eq!(placement_planner().plan(Scatter, 8), Ok(vec![0, 2, 1, 3, 4, 6, 5, 7]));
+		assert_eq!(placement_planner().plan_logical_cores(Scatter, 3), Ok(logical_cores(&[0, 1, 2])));
+	}
+
+	#[test]
+	fn one_per_physical_core_never_uses_smt_siblings()
+	{
+		assert_eq!(placement_planner().plan(OnePerPhysicalCore, 4), Ok(vec![0, 1, 2, 3]));
+		assert_eq!(placement_planner().plan(OnePerPhysicalCore, 5), Err(PlacementError::NotEnoughCores { required: 5, available: 4 }));
+	}
+
+	#[test]
+	fn fill_smt_last_uses_smt_siblings_once_every_physical_core_has_a_worker()
+	{
+		assert_eq!(placement_planner().plan(FillSmtLast, 6), Ok(vec![0, 1, 2, 3, 4, 5]));
+	}
+
+	#[test]
+	fn not_enough_cores()
+	{
+		for &placement_policy in &[Compact, Scatter, FillSmtLast]
+		{
+			assert_eq!(placement_planner().plan(placement_policy, 9), Err(PlacementError::NotEnoughCores { required: 9, available: 8 }));
+		}
+		assert_eq!(placement_planner().plan(Compact, 0), Ok(Vec::new()));
+	}
+}
@@ -0,0 +1,28 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// How a `PlacementPlanner` assigns workers to logical cores.
+///
+/// Each worker is always assigned a different logical core.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PlacementPolicy
+{
+	/// Workers are packed as closely together as possible: simultaneous multi-threading (SMT) siblings are used together, then physical cores sharing a last level cache, then those in the same NUMA node, then NUMA nodes in the same package.
+	///
+	/// Best for workers which share a lot of data.
+	Compact,
+
+	/// Workers are spread round-robin across packages; within a package, each physical core is used before any of their SMT siblings.
+	///
+	/// Best for workers which are limited by memory bandwidth or want as much cache as possible.
+	Scatter,
+
+	/// At most one worker is placed on each physical core, so no worker shares a physical core with an SMT sibling.
+	///
+	/// Physical cores are used in topological order; fails if there are fewer physical cores than workers.
+	OnePerPhysicalCore,
+
+	/// As `OnePerPhysicalCore`, but once every physical core has a worker SMT siblings are used rather than failing.
+	FillSmtLast,
+}
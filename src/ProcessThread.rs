@@ -0,0 +1,151 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// The placement and scheduling of a thread in a process.
+///
+/// Produced by `ProcessThreads::inspect()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessThread
+{
+	/// Linux task identifier (`tid`).
+	pub task_identifier: TaskIdentifier,
+
+	/// Name (`comm`); at most 15 bytes, and truncated if set to something longer.
+	pub name: String,
+
+	/// Logical cores the thread is allowed to run on (`Cpus_allowed_list` in `status`).
+	pub allowed_logical_cores: LogicalCores,
+
+	/// Logical core the thread last ran on (field 39, `processor`, of `stat`).
+	pub last_logical_core: LogicalCoreIdentifier,
+
+	/// Scheduling policy.
+	pub scheduling_policy: SchedulingPolicy,
+
+	/// Number of times the thread gave up its logical core, eg to wait for input or output (`voluntary_ctxt_switches` in `status`).
+	pub voluntary_context_switches: u64,
+
+	/// Number of times the thread was preempted (`nonvoluntary_ctxt_switches` in `status`); a thread which should have a logical core to itself should have few.
+	pub involuntary_context_switches: u64,
+}
+
+impl ProcessThread
+{
+	/// Inspects a thread of a process.
+	///
+	/// Fails with `NotFound` if the thread does not exist (eg it has exited).
+	pub fn inspect(process_identifier: ProcessIdentifier, task_identifier: TaskIdentifier) -> io::Result<Self>
+	{
+		let folder_path = PathBuf::from(format!("/proc/{}/task/{}", process_identifier, task_identifier));
+
+		let name = read_to_string(folder_path.join("comm"))?.trim_end_matches('\n').to_string();
+
+		let stat = read_to_string(folder_path.join("stat"))?;
+		let after_name = match stat.rfind(')')
+		{
+			None => return Err(LogicalCores::invalid_data("stat lacks a name".to_string())),
+			Some(index) => &stat[index + 1 .. ],
+		};
+		// Fields after the name start at field 3 (`state`).
+		let last_logical_core = match after_name.split_whitespace().nth(39 - 3)
+		{
+			None => return Err(LogicalCores::invalid_data("stat lacks processor".to_string())),
+			Some(processor) => LogicalCoreIdentifier::from_str(processor).map_err(|_| LogicalCores::invalid_data(format!("Invalid processor '{}'", processor)))?,
+		};
+
+		let status = read_to_string(folder_path.join("status"))?;
+		let mut allowed_logical_cores = None;
+		let mut voluntary_context_switches = None;
+		let mut involuntary_context_switches = None;
+		for line in status.lines()
+		{
+			let mut name_and_value = line.splitn(2, ':');
+			let name = name_and_value.next().unwrap();
+			let value = match name_and_value.next()
+			{
+				None => continue,
+				Some(value) => value.trim(),
+			};
+
+			match name
+			{
+				"Cpus_allowed_list" => allowed_logical_cores = Some(LogicalCores::from_linux_list_string(value)?),
+				"voluntary_ctxt_switches" => voluntary_context_switches = Some(u64::from_str(value).map_err(|_| LogicalCores::invalid_data(format!("Invalid voluntary_ctxt_switches '{}'", value)))?),
+				"nonvoluntary_ctxt_switches" => involuntary_context_switches = Some(u64::from_str(value).map_err(|_| LogicalCores::invalid_data(format!("Invalid nonvoluntary_ctxt_switches '{}'", value)))?),
+				_ => (),
+			}
+		}
+
+		Ok
+		(
+			Self
+			{
+				task_identifier,
+				name,
+				allowed_logical_cores: allowed_logical_cores.ok_or_else(|| LogicalCores::invalid_data("status lacks Cpus_allowed_list".to_string()))?,
+				last_logical_core,
+				scheduling_policy: SchedulingPolicy::for_task(task_identifier)?,
+				voluntary_context_switches: voluntary_context_switches.ok_or_else(|| LogicalCores::invalid_data("status lacks voluntary_ctxt_switches".to_string()))?,
+				involuntary_context_switches: involuntary_context_switches.ok_or_else(|| LogicalCores::invalid_data("status lacks nonvoluntary_ctxt_switches".to_string()))?,
+			}
+		)
+	}
+}
+
+#[cfg(test)]
+mod process_thread_tests
+{
+	use super::*;
+
+	fn current_task_identifier() -> TaskIdentifier
+	{
+		(unsafe { ::libc::syscall(::libc::SYS_gettid) }) as TaskIdentifier
+	}
+
+	#[test]
+	fn inspect_current_thread()
+	{
+		let logical_core_identifier = LogicalCores::current_logical_core();
+
+		::std::thread::Builder::new().name("inspected".to_string()).spawn(move ||
+		{
+			LogicalCores::set_current_thread_affinity_for_only_logical_core(logical_core_identifier).unwrap();
+			let task_identifier = current_task_identifier();
+
+			let process_thread = ProcessThread::inspect(process::id() as ProcessIdentifier, task_identifier).unwrap();
+
+			assert_eq!(process_thread.task_identifier, task_identifier);
+			assert_eq!(process_thread.name, "inspected");
+			assert_eq!(process_thread.allowed_logical_cores, LogicalCores::from(logical_core_identifier));
+			assert_eq!(process_thread.last_logical_core, logical_core_identifier);
+			assert_eq!(process_thread.scheduling_policy, SchedulingPolicy::for_current_thread().unwrap());
+		}).unwrap().join().unwrap();
+	}
+
+	#[test]
+	fn inspect_current_process_includes_current_thread()
+	{
+		let logical_core_identifier = LogicalCores::current_logical_core();
+
+		::std::thread::Builder::new().name("a-long-thread-name".to_string()).spawn(move ||
+		{
+			LogicalCores::set_current_thread_affinity_for_only_logical_core(logical_core_identifier).unwrap();
+			let task_identifier = current_task_identifier();
+
+			let process_threads = ProcessThreads::inspect_current_process().unwrap();
+
+			assert!(process_threads.windows(2).all(|pair| pair[0].task_identifier < pair[1].task_identifier));
+			let process_thread = process_threads.find_by_task_identifier(task_identifier).unwrap();
+			assert_eq!(process_thread.name, "a-long-thread-n");
+			assert_eq!(process_thread.allowed_logical_cores, LogicalCores::from(logical_core_identifier));
+			assert_eq!(process_threads.find_by_name("a-long-thread-n").count(), 1);
+		}).unwrap().join().unwrap();
+	}
+
+	#[test]
+	fn inspect_missing_thread_is_not_found()
+	{
+		assert_eq!(ProcessThread::inspect(process::id() as ProcessIdentifier, TaskIdentifier::MAX).unwrap_err().kind(), io::ErrorKind::NotFound);
+	}
+}
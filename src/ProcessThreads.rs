@@ -0,0 +1,83 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// The placement and scheduling of all threads in a process, in ascending order of task identifier (`tid`).
+///
+/// Useful to audit that threads actually ended up where they were configured to be; see also `PlacementAudit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessThreads(Vec<ProcessThread>);
+
+impl Deref for ProcessThreads
+{
+	type Target = [ProcessThread];
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		&self.0[..]
+	}
+}
+
+impl From<ProcessThreads> for Vec<ProcessThread>
+{
+	#[inline(always)]
+	fn from(process_threads: ProcessThreads) -> Self
+	{
+		process_threads.0
+	}
+}
+
+impl ProcessThreads
+{
+	/// Inspects all threads of the current process.
+	#[inline(always)]
+	pub fn inspect_current_process() -> io::Result<Self>
+	{
+		Self::inspect(process::id() as ProcessIdentifier)
+	}
+
+	/// Inspects all threads of a process, as listed in `/proc/<process_identifier>/task`.
+	///
+	/// Threads which exit during inspection are omitted.
+	///
+	/// Slow as it will read several files in `/proc/<process_identifier>/task/<tid>` for each thread.
+	pub fn inspect(process_identifier: ProcessIdentifier) -> io::Result<Self>
+	{
+		let mut task_identifiers = Vec::new();
+		for directory_entry in read_dir(format!("/proc/{}/task", process_identifier))?
+		{
+			if let Some(task_identifier) = directory_entry?.file_name().to_str().and_then(|file_name| TaskIdentifier::from_str(file_name).ok())
+			{
+				task_identifiers.push(task_identifier)
+			}
+		}
+		task_identifiers.sort();
+
+		let mut process_threads = Vec::with_capacity(task_identifiers.len());
+		for task_identifier in task_identifiers
+		{
+			match ProcessThread::inspect(process_identifier, task_identifier)
+			{
+				Ok(process_thread) => process_threads.push(process_thread),
+				Err(ref error) if error.kind() == io::ErrorKind::NotFound || error.raw_os_error() == Some(ESRCH) => continue,
+				Err(error) => return Err(error),
+			}
+		}
+		Ok(ProcessThreads(process_threads))
+	}
+
+	/// Finds a thread by task identifier (`tid`).
+	#[inline(always)]
+	pub fn find_by_task_identifier(&self, task_identifier: TaskIdentifier) -> Option<&ProcessThread>
+	{
+		self.0.iter().find(|process_thread| process_thread.task_identifier == task_identifier)
+	}
+
+	/// Finds threads by name (`comm`).
+	#[inline(always)]
+	pub fn find_by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item=&'a ProcessThread> + 'a
+	{
+		self.0.iter().filter(move |process_thread| process_thread.name == name)
+	}
+}
@@ -0,0 +1,316 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// An opt-in rebalancer of worker threads pinned to one logical core each, which moves them between logical cores in a pool when load is imbalanced.
+///
+/// Workers are recorded using `register_worker()`; `rebalance()` should then be called periodically, eg once a second from a monitoring thread.
+/// Each call samples `CoreUtilisation` and each worker's `ThreadSchedStats`.
+/// A pinned worker's own load moves with it, so what matters is the load on its logical core from everything else (other workers, other processes, interrupts); this is the logical core's busy fraction less the worker's own share.
+/// If that is more than `imbalance_threshold` greater than the busy fraction of a logical core in the pool which no worker owns, the worker is moved to the nearest (by topology) such logical core.
+///
+/// To avoid moving workers back and forth (hysteresis), at most one worker is moved per call, only to a logical core less busy than the load it is moving away from, and a moved worker is not moved again for `cooldown_periods` calls.
+#[derive(Debug, Clone)]
+pub struct Rebalancer
+{
+	planner: PlacementPlanner,
+	imbalance_threshold: f64,
+	cooldown_periods: u32,
+	workers: BTreeMap<TaskIdentifier, (LogicalCoreIdentifier, u32)>,
+	previous_sample: Option<(Instant, CoreUtilisation)>,
+	previous_worker_statistics: BTreeMap<TaskIdentifier, ThreadSchedStats>,
+}
+
+impl Rebalancer
+{
+	/// Creates a new instance which moves workers within the pool of `planner`, with no workers.
+	///
+	/// `imbalance_threshold` is a difference in busy fraction, from `0.0` to `1.0`; `0.25` is a reasonable choice.
+	#[inline(always)]
+	pub fn new(planner: PlacementPlanner, imbalance_threshold: f64, cooldown_periods: u32) -> Self
+	{
+		Self
+		{
+			planner,
+			imbalance_threshold,
+			cooldown_periods,
+			workers: BTreeMap::new(),
+			previous_sample: None,
+			previous_worker_statistics: BTreeMap::new(),
+		}
+	}
+
+	/// Records a worker thread of the current process, identified by its Linux task identifier (`tid`), which has been pinned to `logical_core_identifier`, eg using a `PlacementPlanner` assignment.
+	///
+	/// The worker is not moved until its share of its logical core is known, ie until two calls of `rebalance()` have been made.
+	#[inline(always)]
+	pub fn register_worker(&mut self, task_identifier: TaskIdentifier, logical_core_identifier: LogicalCoreIdentifier)
+	{
+		self.workers.insert(task_identifier, (logical_core_identifier, 0));
+		self.previous_worker_statistics.remove(&task_identifier);
+	}
+
+	/// Forgets a worker thread, eg because it is exiting.
+	///
+	/// Returns the logical core it was last pinned to.
+	#[inline(always)]
+	pub fn unregister_worker(&mut self, task_identifier: TaskIdentifier) -> Option<LogicalCoreIdentifier>
+	{
+		self.previous_worker_statistics.remove(&task_identifier);
+		self.workers.remove(&task_identifier).map(|(logical_core_identifier, _cooldown)| logical_core_identifier)
+	}
+
+	/// Iterates over the worker threads and the logical cores they are pinned to, in ascending order of task identifier.
+	#[inline(always)]
+	pub fn workers<'a>(&'a self) -> impl Iterator<Item=(TaskIdentifier, LogicalCoreIdentifier)> + 'a
+	{
+		self.workers.iter().map(|(task_identifier, &(logical_core_identifier, _cooldown))| (*task_identifier, logical_core_identifier))
+	}
+
+	/// Samples utilisation and moves at most one worker thread if load is imbalanced.
+	///
+	/// `before_move` is called before a worker thread is moved, eg so the worker can migrate its state in a `PerLogicalCoreData`; if it returns `false` the worker is not moved.
+	///
+	/// The first call only takes a sample and so never moves a worker thread.
+	/// A worker thread which no longer exists is forgotten rather than moved.
+	pub fn rebalance(&mut self, mut before_move: impl FnMut(&RebalancingMove) -> bool) -> io::Result<Option<RebalancingMove>>
+	{
+		let now = Instant::now();
+		let sample = CoreUtilisation::sample()?;
+
+		let mut worker_statistics = BTreeMap::new();
+		let mut exited = Vec::new();
+		for &task_identifier in self.workers.keys()
+		{
+			match ThreadSchedStats::for_task(task_identifier)
+			{
+				Ok(statistics) =>
+				{
+					worker_statistics.insert(task_identifier, statistics);
+				}
+
+				Err(ref error) if error.kind() == io::ErrorKind::NotFound => exited.push(task_identifier),
+
+				Err(error) => return Err(error),
+			}
+		}
+		for task_identifier in exited
+		{
+			self.unregister_worker(task_identifier);
+		}
+
+		let previous_worker_statistics = replace(&mut self.previous_worker_statistics, worker_statistics);
+		let (previous_instant, previous_sample) = match self.previous_sample.replace((now, sample))
+		{
+			None => return Ok(None),
+			Some(previous_sample) => previous_sample,
+		};
+
+		let since = self.previous_sample.as_ref().unwrap().1.since(&previous_sample);
+
+		let elapsed_nanoseconds = now.duration_since(previous_instant).as_nanos() as f64;
+		let worker_busy_fractions: BTreeMap<TaskIdentifier, f64> = self.previous_worker_statistics.iter().filter_map(|(task_identifier, statistics)| previous_worker_statistics.get(task_identifier).map(|previous_statistics| (*task_identifier, (statistics.since(previous_statistics).time_on_logical_core.as_nanos() as f64) / elapsed_nanoseconds))).collect();
+
+		let rebalancing_move = match self.decide(&since, &worker_busy_fractions)
+		{
+			None => return Ok(None),
+			Some(rebalancing_move) => rebalancing_move,
+		};
+
+		if !before_move(&rebalancing_move)
+		{
+			return Ok(None)
+		}
+
+		match LogicalCores::from(rebalancing_move.to).set_task_affinity(rebalancing_move.task_identifier)
+		{
+			Ok(()) =>
+			{
+				self.record_move(&rebalancing_move);
+				Ok(Some(rebalancing_move))
+			}
+
+			Err(error) => if error.raw_os_error() == Some(ESRCH)
+			{
+				self.unregister_worker(rebalancing_move.task_identifier);
+				Ok(None)
+			}
+			else
+			{
+				Err(error)
+			}
+		}
+	}
+
+	/// Starts a period, then decides which worker, if any, to move.
+	///
+	/// `since` is the times of each logical core over the period; `worker_busy_fractions` is each worker's share of its logical core over the period, and workers without one are not moved.
+	fn decide(&mut self, since: &PerLogicalCoreData<CpuTimes>, worker_busy_fractions: &BTreeMap<TaskIdentifier, f64>) -> Option<RebalancingMove>
+	{
+		for &mut (_logical_core_identifier, ref mut cooldown) in self.workers.values_mut()
+		{
+			*cooldown = cooldown.saturating_sub(1);
+		}
+
+		let busy_fraction = |logical_core_identifier: LogicalCoreIdentifier| since.get(logical_core_identifier).map(|cpu_times| cpu_times.busy_fraction());
+
+		let mut most_contended: Option<(f64, TaskIdentifier, LogicalCoreIdentifier)> = None;
+		for (&task_identifier, &(logical_core_identifier, cooldown)) in self.workers.iter()
+		{
+			if cooldown != 0
+			{
+				continue
+			}
+
+			let (busy_fraction, worker_busy_fraction) = match (busy_fraction(logical_core_identifier), worker_busy_fractions.get(&task_identifier))
+			{
+				(Some(busy_fraction), Some(worker_busy_fraction)) => (busy_fraction, *worker_busy_fraction),
+				_ => continue,
+			};
+
+			let other_busy_fraction = (busy_fraction - worker_busy_fraction).max(0.0);
+			match most_contended
+			{
+				Some((most_contended_fraction, _, _)) if most_contended_fraction >= other_busy_fraction => (),
+				_ => most_contended = Some((other_busy_fraction, task_identifier, logical_core_identifier)),
+			}
+		}
+		let (other_busy_fraction, task_identifier, from) = most_contended?;
+
+		let owned: BTreeSet<LogicalCoreIdentifier> = self.workers.values().map(|&(logical_core_identifier, _cooldown)| logical_core_identifier).collect();
+		let candidates = self.planner.pool().iter().cloned().filter(|logical_core_identifier| !owned.contains(logical_core_identifier) && match busy_fraction(*logical_core_identifier)
+		{
+			None => false,
+			Some(busy_fraction) => other_busy_fraction - busy_fraction > self.imbalance_threshold,
+		});
+
+		self.planner.topology().nearest_logical_core(from, candidates).map(|to| RebalancingMove { task_identifier, from, to })
+	}
+
+	#[inline(always)]
+	fn record_move(&mut self, rebalancing_move: &RebalancingMove)
+	{
+		self.workers.insert(rebalancing_move.task_identifier, (rebalancing_move.to, self.cooldown_periods));
+	}
+}
+
+#[cfg(test)]
+mod rebalancer_tests
+{
+	use super::*;
+
+	const Threshold: f64 = 0.25;
+
+	const CooldownPeriods: u32 = 2;
+
+	const NumberOfPeriods: usize = 20;
+
+	fn rebalancer(number_of_logical_cores: LogicalCoreIdentifier) -> Rebalancer
+	{
+		let pool = LogicalCores::from((0 .. number_of_logical_cores).collect::<BTreeSet<_>>());
+		let topology = Topology::from(PerLogicalCoreData::new(&pool, LogicalCoreTopology::unknown));
+		Rebalancer::new(PlacementPlanner::new(pool, topology), Threshold, CooldownPeriods)
+	}
+
+	/// Simulates `NumberOfPeriods` periods.
+	///
+	/// `other_load` is the load on each logical core from outside the workers; `worker_demands` is the load each worker would place on a logical core it had to itself.
+	/// Workers share a logical core with other load in proportion to their demand.
+	fn simulate(rebalancer: &mut Rebalancer, other_load: &[f64], worker_demands: &BTreeMap<TaskIdentifier, f64>) -> Vec<RebalancingMove>
+	{
+		let mut moves = Vec::new();
+		for _period in 0 .. NumberOfPeriods
+		{
+			let mut worker_busy_fractions = BTreeMap::new();
+			let mut busy_fractions = other_load.to_vec();
+			for (logical_core_identifier, other_load) in other_load.iter().enumerate()
+			{
+				let workers: Vec<(TaskIdentifier, f64)> = rebalancer.workers().filter(|&(_, worker_logical_core_identifier)| worker_logical_core_identifier as usize == logical_core_identifier).map(|(task_identifier, _)| (task_identifier, worker_demands[&task_identifier])).collect();
+				let total_demand = other_load + workers.iter().map(|&(_, demand)| demand).sum::<f64>();
+				let scale = if total_demand > 1.0 { 1.0 / total_demand } else { 1.0 };
+				for (task_identifier, demand) in workers
+				{
+					worker_busy_fractions.insert(task_identifier, demand * scale);
+				}
+				busy_fractions[logical_core_identifier] = total_demand.min(1.0);
+			}
+
+			let since: PerLogicalCoreData<CpuTimes> = busy_fractions.iter().enumerate().map(|(logical_core_identifier, busy_fraction)|
+			{
+				let busy = (busy_fraction * 1000.0).round() as u64;
+				(logical_core_identifier as LogicalCoreIdentifier, CpuTimes { user: busy, idle: 1000 - busy, .. CpuTimes::default() })
+			}).collect();
+
+			if let Some(rebalancing_move) = rebalancer.decide(&since, &worker_busy_fractions)
+			{
+				rebalancer.record_move(&rebalancing_move);
+				moves.push(rebalancing_move);
+			}
+		}
+		moves
+	}
+
+	#[test]
+	fn does_not_move_a_worker_away_from_its_own_load()
+	{
+		let mut rebalancer = rebalancer(2);
+		rebalancer.register_worker(100, 0);
+
+		let worker_demands = vec![(100, 1.0)].into_iter().collect();
+		assert_eq!(simulate(&mut rebalancer, &[0.0, 0.0], &worker_demands), Vec::new());
+		assert_eq!(rebalancer.workers().collect::<Vec<_>>(), vec![(100, 0)]);
+	}
+
+	#[test]
+	fn moves_a_worker_away_from_other_load_once()
+	{
+		let mut rebalancer = rebalancer(2);
+		rebalancer.register_worker(100, 0);
+
+		let worker_demands = vec![(100, 1.0)].into_iter().collect();
+		assert_eq!(simulate(&mut rebalancer, &[0.6, 0.0], &worker_demands), vec![RebalancingMove { task_identifier: 100, from: 0, to: 1 }]);
+		assert_eq!(rebalancer.workers().collect::<Vec<_>>(), vec![(100, 1)]);
+	}
+
+	#[test]
+	fn separates_workers_sharing_a_logical_core_once()
+	{
+		let mut rebalancer = rebalancer(2);
+		rebalancer.register_worker(100, 0);
+		rebalancer.register_worker(101, 0);
+
+		let worker_demands = vec![(100, 1.0), (101, 1.0)].into_iter().collect();
+		assert_eq!(simulate(&mut rebalancer, &[0.0, 0.0], &worker_demands), vec![RebalancingMove { task_identifier: 100, from: 0, to: 1 }]);
+	}
+
+	#[test]
+	fn does_not_stack_workers_on_a_briefly_idle_logical_core()
+	{
+		let worker_demands = vec![(100, 1.0), (101, 0.0)].into_iter().collect();
+
+		let mut rebalancer_without_free_logical_core = rebalancer(2);
+		rebalancer_without_free_logical_core.register_worker(100, 0);
+		rebalancer_without_free_logical_core.register_worker(101, 1);
+		assert_eq!(simulate(&mut rebalancer_without_free_logical_core, &[0.6, 0.0], &worker_demands), Vec::new());
+
+		let mut rebalancer_with_free_logical_core = rebalancer(3);
+		rebalancer_with_free_logical_core.register_worker(100, 0);
+		rebalancer_with_free_logical_core.register_worker(101, 1);
+		assert_eq!(simulate(&mut rebalancer_with_free_logical_core, &[0.6, 0.0, 0.1], &worker_demands), vec![RebalancingMove { task_identifier: 100, from: 0, to: 2 }]);
+	}
+
+	#[test]
+	fn moves_a_worker_between_sparse_logical_cores()
+	{
+		let pool = logical_cores(&[0, 2]);
+		let topology = Topology::from(PerLogicalCoreData::new(&pool, LogicalCoreTopology::unknown));
+		let mut rebalancer = Rebalancer::new(PlacementPlanner::new(pool, topology), Threshold, CooldownPeriods);
+		rebalancer.register_worker(100, 0);
+
+		let previous = CoreUtilisation::parse_proc_stat("cpu0 0 0 0 0\ncpu2 0 0 0 0\n").unwrap();
+		let current = CoreUtilisation::parse_proc_stat("cpu0 1000 0 0 0\ncpu2 0 0 0 1000\n").unwrap();
+		let worker_busy_fractions = vec![(100, 0.4)].into_iter().collect();
+
+		assert_eq!(rebalancer.decide(&current.since(&previous), &worker_busy_fractions), Some(RebalancingMove { task_identifier: 100, from: 0, to: 2 }));
+	}
+}
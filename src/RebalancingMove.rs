@@ -0,0 +1,26 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A move of a pinned worker thread from one logical core to another by a `Rebalancer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RebalancingMove
+{
+	/// Linux task identifier (`tid`) of the worker thread.
+	pub task_identifier: TaskIdentifier,
+
+	/// Logical core the worker thread is pinned to.
+	pub from: LogicalCoreIdentifier,
+
+	/// Logical core the worker thread is to be pinned to.
+	pub to: LogicalCoreIdentifier,
+}
+
+impl Display for RebalancingMove
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "Thread {} from logical core {} to logical core {}", self.task_identifier, self.from, self.to)
+	}
+}
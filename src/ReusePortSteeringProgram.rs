@@ -0,0 +1,244 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A classic Berkeley Packet Filter (cBPF) program for `SO_ATTACH_REUSEPORT_CBPF` which steers each incoming connection (or datagram) to the listening socket owned by the worker on the logical core which received it.
+///
+/// This is the standard technique for per-core accept loops: each worker has its own listening socket in a `SO_REUSEPORT` group, and so processes connections on the same logical core that handled their packets.
+///
+/// The kernel identifies the sockets in a `SO_REUSEPORT` group by the order in which they were added to it (ie bound or, for TCP, began listening); this program assumes they were added in ascending order of logical core, as yielded by `PerLogicalCoreData::iter()`.
+/// Packets received on a logical core which has no socket are steered to one chosen by the kernel's usual hash.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReusePortSteeringProgram(Vec<ClassicBpfInstruction>);
+
+impl Deref for ReusePortSteeringProgram
+{
+	type Target = [ClassicBpfInstruction];
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		&self.0[..]
+	}
+}
+
+impl ReusePortSteeringProgram
+{
+	/// Creates a new instance for a socket per logical core.
+	///
+	/// Fails if there are too many logical cores for a classic BPF program (about 2,000).
+	#[inline(always)]
+	pub fn new<Socket>(sockets: &PerLogicalCoreData<Socket>) -> io::Result<Self>
+	{
+		let logical_core_identifiers: Vec<LogicalCoreIdentifier> = sockets.logical_core_indices().collect();
+		let number_of_sockets = logical_core_identifiers.len();
+
+		let is_contiguous = number_of_sockets != 0 && (logical_core_identifiers[number_of_sockets - 1] - logical_core_identifiers[0]) as usize == number_of_sockets - 1;
+		if is_contiguous
+		{
+			let mut instructions = vec![ClassicBpfInstruction::load_logical_core()];
+			let first = logical_core_identifiers[0];
+			if first != 0
+			{
+				instructions.push(ClassicBpfInstruction::subtract(first as u32));
+			}
+			instructions.push(ClassicBpfInstruction::return_accumulator());
+			return Ok(ReusePortSteeringProgram(instructions))
+		}
+
+		Self::from_socket_indices(logical_core_identifiers.iter().enumerate().map(|(socket_index, logical_core_identifier)| (*logical_core_identifier, socket_index as u32)))
+	}
+
+	/// Creates a new instance for a socket per logical core, steering packets received on any of `all_logical_cores` without a socket to the socket of the nearest logical core which has one, as found by `Topology::nearest_logical_core()`.
+	///
+	/// `all_logical_cores` is typically `LogicalCores::online()`, and `topology` should be discovered from it.
+	///
+	/// Fails if there are too many logical cores for a classic BPF program (about 2,000).
+	#[inline(always)]
+	pub fn new_with_nearest_fallback<Socket>(sockets: &PerLogicalCoreData<Socket>, all_logical_cores: &LogicalCores, topology: &Topology) -> io::Result<Self>
+	{
+		let socket_indices: BTreeMap<LogicalCoreIdentifier, u32> = sockets.logical_core_indices().enumerate().map(|(socket_index, logical_core_identifier)| (logical_core_identifier, socket_index as u32)).collect();
+
+		let mut mapping = socket_indices.clone();
+		for logical_core_identifier in all_logical_cores.iter()
+		{
+			if !mapping.contains_key(logical_core_identifier)
+			{
+				if let Some(nearest) = topology.nearest_logical_core(*logical_core_identifier, socket_indices.keys().cloned())
+				{
+					mapping.insert(*logical_core_identifier, socket_indices[&nearest]);
+				}
+			}
+		}
+
+		Self::from_socket_indices(mapping.into_iter())
+	}
+
+	/// Attaches this program to a `SO_REUSEPORT` group by attaching it to any one of its sockets.
+	///
+	/// Requires Linux 4.5 or later.
+	#[inline(always)]
+	pub fn attach(&self, socket_file_descriptor: RawFd) -> io::Result<()>
+	{
+		let program = sock_fprog
+		{
+			len: self.0.len() as u16,
+			filter: self.0.as_ptr(),
+		};
+
+		let result = unsafe { setsockopt(socket_file_descriptor, SOL_SOCKET, SO_ATTACH_REUSEPORT_CBPF, &program as *const sock_fprog as *const c_void, size_of::<sock_fprog>() as socklen_t) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(LogicalCores::last_os_error())
+		}
+	}
+
+	/// A chain of `jeq #logical_core, ret #socket_index`; anything else returns an invalid socket index, so the kernel falls back to its usual hash.
+	#[inline(always)]
+	fn from_socket_indices(socket_indices: impl Iterator<Item=(LogicalCoreIdentifier, u32)>) -> io::Result<Self>
+	{
+		let mut instructions = vec![ClassicBpfInstruction::load_logical_core()];
+		for (logical_core_identifier, socket_index) in socket_indices
+		{
+			instructions.push(ClassicBpfInstruction::jump_if_equal(logical_core_identifier as u32, 0, 1));
+			instructions.push(ClassicBpfInstruction::return_constant(socket_index));
+		}
+		instructions.push(ClassicBpfInstruction::return_constant(u32::MAX));
+
+		if unlikely!(instructions.len() > BPF_MAXINSNS)
+		{
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("A classic BPF program of {} instructions is too long", instructions.len())))
+		}
+
+		Ok(ReusePortSteeringProgram(instructions))
+	}
+}
+
+#[cfg(test)]
+mod reuse_port_steering_program_tests
+{
+	use super::*;
+	use ::libc::AF_INET;
+	use ::libc::bind;
+	use ::libc::cpu_set_t;
+	use ::libc::CPU_SET;
+	use ::libc::CPU_ZERO;
+	use ::libc::getsockname;
+	use ::libc::in_addr;
+	use ::libc::sched_getcpu;
+	use ::libc::sched_setaffinity;
+	use ::libc::sockaddr;
+	use ::libc::sockaddr_in;
+	use ::libc::socket;
+	use ::libc::SO_REUSEPORT;
+	use ::libc::SOCK_DGRAM;
+	use ::std::mem::zeroed;
+	use ::std::net::UdpSocket;
+	use ::std::os::unix::io::AsRawFd;
+	use ::std::os::unix::io::FromRawFd;
+
+	const NumberOfSockets: usize = 3;
+
+	const NumberOfDatagrams: usize = 16;
+
+	/// Pins the current thread to the logical core it is running on, so that loopback datagrams it sends are received on that logical core.
+	fn pin_to_current_logical_core() -> LogicalCoreIdentifier
+	{
+		unsafe
+		{
+			let logical_core_identifier = sched_getcpu();
+			assert!(logical_core_identifier >= 0);
+
+			let mut cpu_set: cpu_set_t = zeroed();
+			CPU_ZERO(&mut cpu_set);
+			CPU_SET(logical_core_identifier as usize, &mut cpu_set);
+			assert_eq!(sched_setaffinity(0, size_of::<cpu_set_t>(), &cpu_set), 0, "{}", io::Error::last_os_error());
+
+			logical_core_identifier as LogicalCoreIdentifier
+		}
+	}
+
+	/// Binds `NumberOfSockets` `SO_REUSEPORT` UDP sockets to the same port on `127.0.0.1`, in order of socket index.
+	fn reuse_port_group() -> (Vec<UdpSocket>, u16)
+	{
+		let mut port: u16 = 0;
+		let mut sockets = Vec::with_capacity(NumberOfSockets);
+		for _socket_index in 0 .. NumberOfSockets
+		{
+			unsafe
+			{
+				let socket_file_descriptor = socket(AF_INET, SOCK_DGRAM, 0);
+				assert_ne!(socket_file_descriptor, -1, "{}", io::Error::last_os_error());
+				let socket = UdpSocket::from_raw_fd(socket_file_descriptor);
+
+				let enable: c_int = 1;
+				assert_eq!(setsockopt(socket_file_descriptor, SOL_SOCKET, SO_REUSEPORT, &enable as *const c_int as *const c_void, size_of::<c_int>() as socklen_t), 0, "{}", io::Error::last_os_error());
+
+				let mut address: sockaddr_in = zeroed();
+				address.sin_family = AF_INET as _;
+				address.sin_port = port.to_be();
+				address.sin_addr = in_addr { s_addr: 0x7F00_0001u32.to_be() };
+				assert_eq!(bind(socket_file_descriptor, &address as *const sockaddr_in as *const sockaddr, size_of::<sockaddr_in>() as socklen_t), 0, "{}", io::Error::last_os_error());
+
+				let mut address_length = size_of::<sockaddr_in>() as socklen_t;
+				assert_eq!(getsockname(socket_file_descriptor, &mut address as *mut sockaddr_in as *mut sockaddr, &mut address_length), 0, "{}", io::Error::last_os_error());
+				port = u16::from_be(address.sin_port);
+
+				socket.set_nonblocking(true).unwrap();
+				sockets.push(socket);
+			}
+		}
+		(sockets, port)
+	}
+
+	/// Sends `NumberOfDatagrams` datagrams, each from a different source port so that the kernel's hash varies, and returns the number received by each socket.
+	fn send_datagrams(program: &ReusePortSteeringProgram) -> Vec<usize>
+	{
+		let (sockets, port) = reuse_port_group();
+		program.attach(sockets[0].as_raw_fd()).unwrap();
+
+		for _datagram_index in 0 .. NumberOfDatagrams
+		{
+			let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+			sender.send_to(b"datagram", ("127.0.0.1", port)).unwrap();
+		}
+
+		let mut buffer = [0u8; 16];
+		sockets.iter().map(|socket|
+		{
+			let mut received = 0;
+			while socket.recv(&mut buffer).is_ok()
+			{
+				received += 1;
+			}
+			received
+		}).collect()
+	}
+
+	#[test]
+	fn steers_to_socket_of_receiving_logical_core()
+	{
+		let logical_core_identifier = pin_to_current_logical_core();
+
+		let contiguous = PerLogicalCoreData::new(&LogicalCores::from((logical_core_identifier .. logical_core_identifier + NumberOfSockets as LogicalCoreIdentifier).collect::<BTreeSet<_>>()), |_| ());
+		assert_eq!(send_datagrams(&ReusePortSteeringProgram::new(&contiguous).unwrap()), vec![NumberOfDatagrams, 0, 0]);
+
+		let steered_to_last = ReusePortSteeringProgram::from_socket_indices(vec![(logical_core_identifier, (NumberOfSockets - 1) as u32)].into_iter()).unwrap();
+		assert_eq!(send_datagrams(&steered_to_last), vec![0, 0, NumberOfDatagrams]);
+	}
+
+	#[test]
+	fn falls_back_to_hash_for_out_of_range_socket_index()
+	{
+		let logical_core_identifier = pin_to_current_logical_core();
+
+		let out_of_range = ReusePortSteeringProgram::from_socket_indices(vec![(logical_core_identifier, NumberOfSockets as u32)].into_iter()).unwrap();
+		let received = send_datagrams(&out_of_range);
+		assert_eq!(received.iter().sum::<usize>(), NumberOfDatagrams);
+		assert!(received.iter().filter(|&&received| received != 0).count() > 1, "Datagrams were not spread by hash: {:?}", received);
+	}
+}
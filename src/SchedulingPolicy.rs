@@ -0,0 +1,335 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A Linux scheduling policy and its priority or parameters.
+///
+/// Usually configured together with affinity for data-plane threads, eg `Fifo` on a logical core from `LogicalCores::isolated_for_process()`.
+///
+/// Setting a real-time (`Fifo` or `RoundRobin`) or `Deadline` policy, or lowering nice, requires root or `CAP_SYS_NICE` (or a suitable `RLIMIT_RTPRIO` or `RLIMIT_NICE`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SchedulingPolicy
+{
+	/// The default time-sharing policy (`SCHED_OTHER`, also known as `SCHED_NORMAL`).
+	Other
+	{
+		/// Nice, from -20 (highest priority) to 19 (lowest priority).
+		nice: i8,
+	},
+
+	/// Time-sharing for CPU-intensive, non-interactive threads (`SCHED_BATCH`).
+	Batch
+	{
+		/// Nice, from -20 (highest priority) to 19 (lowest priority).
+		nice: i8,
+	},
+
+	/// Only runs when there is nothing else to run (`SCHED_IDLE`); nice is reset to 0.
+	Idle,
+
+	/// Real-time, first-in first-out (`SCHED_FIFO`), with a priority from 1 (lowest) to 99 (highest).
+	Fifo(u8),
+
+	/// Real-time, round-robin (`SCHED_RR`), with a priority from 1 (lowest) to 99 (highest).
+	RoundRobin(u8),
+
+	/// Earliest deadline first (`SCHED_DEADLINE`); requires `runtime <= deadline <= period`.
+	///
+	/// Only the current thread or a `TaskIdentifier` can have this policy set, and a thread with this policy must have an affinity for all logical cores in its root domain (usually all of them).
+	Deadline
+	{
+		/// Execution time to reserve in each period.
+		runtime: Duration,
+
+		/// Relative deadline of each period.
+		deadline: Duration,
+
+		/// Period; if zero, the same as `deadline`.
+		period: Duration,
+	},
+
+	/// A policy not known to this crate (eg `SCHED_EXT`, added in Linux 6.12), by number.
+	///
+	/// Reported so that inspecting a thread with such a policy does not fail; setting it passes the number to the kernel with no priority or parameters.
+	Unknown(u32),
+}
+
+impl Default for SchedulingPolicy
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		SchedulingPolicy::Other { nice: 0 }
+	}
+}
+
+impl SchedulingPolicy
+{
+	/// Sets the scheduling policy of the current thread.
+	#[inline(always)]
+	pub fn set_for_current_thread(&self) -> io::Result<()>
+	{
+		self.set_for_task(0)
+	}
+
+	/// Sets the scheduling policy of a thread identified by its Linux task identifier (`tid`); `0` is the current thread.
+	#[inline(always)]
+	pub fn set_for_task(&self, task_identifier: TaskIdentifier) -> io::Result<()>
+	{
+		let attributes = self.to_sched_attr()?;
+		Self::set_attributes(task_identifier, &attributes)
+	}
+
+	/// Sets the scheduling policy of a thread identified by its `pthread_t`.
+	///
+	/// Fails with `InvalidInput` for `Other` or `Batch` with a nice other than 0 and for `Deadline` and `Unknown`, which can only be set using a `TaskIdentifier`.
+	pub fn set_for_thread(&self, thread_identifier: ThreadIdentifier) -> io::Result<()>
+	{
+		use self::SchedulingPolicy::*;
+
+		let (policy, priority) = match *self
+		{
+			Other { nice: 0 } => (SCHED_OTHER, 0),
+			Batch { nice: 0 } => (SCHED_BATCH, 0),
+			Other { .. } | Batch { .. } => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Nice can only be set using a TaskIdentifier")),
+			Idle => (SCHED_IDLE, 0),
+			Fifo(priority) => (SCHED_FIFO, priority),
+			RoundRobin(priority) => (SCHED_RR, priority),
+			Deadline { .. } => return Err(io::Error::new(io::ErrorKind::InvalidInput, "SCHED_DEADLINE can only be set using a TaskIdentifier")),
+			Unknown(_) => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Unknown scheduling policies can only be set using a TaskIdentifier")),
+		};
+
+		let parameters = sched_param
+		{
+			sched_priority: priority as c_int,
+		};
+		let result = unsafe { pthread_setschedparam(thread_identifier, policy, &parameters) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(io::Error::from_raw_os_error(result))
+		}
+	}
+
+	/// Gets the scheduling policy of the current thread.
+	#[inline(always)]
+	pub fn for_current_thread() -> io::Result<Self>
+	{
+		Self::for_task(0)
+	}
+
+	/// Gets the scheduling policy of a thread identified by its Linux task identifier (`tid`); `0` is the current thread.
+	#[inline(always)]
+	pub fn for_task(task_identifier: TaskIdentifier) -> io::Result<Self>
+	{
+		Ok(Self::from_sched_attr(&Self::get_attributes(task_identifier)?))
+	}
+
+	/// Gets the scheduling policy of a thread identified by its `pthread_t`.
+	///
+	/// Nice can not be obtained using a `pthread_t`, so is always 0 for `Other` and `Batch`; fails with `InvalidData` for `Deadline`, as its parameters can only be obtained using a `TaskIdentifier`.
+	/// Policies not known to this crate are `Unknown`.
+	/// Some libc implementations (eg glibc) cache the policy once obtained, so it may be stale if later set using a `TaskIdentifier`.
+	pub fn for_thread(thread_identifier: ThreadIdentifier) -> io::Result<Self>
+	{
+		use self::SchedulingPolicy::*;
+
+		let mut policy: c_int = 0;
+		let mut parameters = sched_param
+		{
+			sched_priority: 0,
+		};
+		let result = unsafe { pthread_getschedparam(thread_identifier, &mut policy, &mut parameters) };
+		if unlikely!(result != 0)
+		{
+			return Err(io::Error::from_raw_os_error(result))
+		}
+
+		let priority = parameters.sched_priority as u8;
+		match policy & !SCHED_RESET_ON_FORK
+		{
+			SCHED_OTHER => Ok(Other { nice: 0 }),
+			SCHED_BATCH => Ok(Batch { nice: 0 }),
+			SCHED_IDLE => Ok(Idle),
+			SCHED_FIFO => Ok(Fifo(priority)),
+			SCHED_RR => Ok(RoundRobin(priority)),
+			SCHED_DEADLINE => Err(io::Error::new(io::ErrorKind::InvalidData, "SCHED_DEADLINE parameters can only be obtained using a TaskIdentifier")),
+			policy => Ok(Unknown(policy as u32)),
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn get_attributes(task_identifier: TaskIdentifier) -> io::Result<sched_attr>
+	{
+		let mut attributes = sched_attr::default();
+		let result = unsafe { sched_getattr(task_identifier, &mut attributes, size_of::<sched_attr>() as c_uint, 0) };
+		if likely!(result == 0)
+		{
+			Ok(attributes)
+		}
+		else
+		{
+			Err(LogicalCores::last_os_error())
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn set_attributes(task_identifier: TaskIdentifier, attributes: &sched_attr) -> io::Result<()>
+	{
+		let result = unsafe { sched_setattr(task_identifier, attributes, 0) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(LogicalCores::last_os_error())
+		}
+	}
+
+	#[inline(always)]
+	fn to_sched_attr(self) -> io::Result<sched_attr>
+	{
+		use self::SchedulingPolicy::*;
+
+		#[inline(always)]
+		fn nanoseconds(duration: Duration) -> io::Result<u64>
+		{
+			let nanoseconds = duration.as_nanos();
+			if unlikely!(nanoseconds > (u64::MAX as u128))
+			{
+				Err(io::Error::new(io::ErrorKind::InvalidInput, "Duration is too large"))
+			}
+			else
+			{
+				Ok(nanoseconds as u64)
+			}
+		}
+
+		let mut attributes = sched_attr
+		{
+			size: size_of::<sched_attr>() as u32,
+			.. sched_attr::default()
+		};
+
+		match self
+		{
+			Other { nice } =>
+			{
+				attributes.sched_policy = SCHED_OTHER as u32;
+				attributes.sched_nice = nice as i32;
+			}
+
+			Batch { nice } =>
+			{
+				attributes.sched_policy = SCHED_BATCH as u32;
+				attributes.sched_nice = nice as i32;
+			}
+
+			Idle => attributes.sched_policy = SCHED_IDLE as u32,
+
+			Fifo(priority) =>
+			{
+				attributes.sched_policy = SCHED_FIFO as u32;
+				attributes.sched_priority = priority as u32;
+			}
+
+			RoundRobin(priority) =>
+			{
+				attributes.sched_policy = SCHED_RR as u32;
+				attributes.sched_priority = priority as u32;
+			}
+
+			Deadline { runtime, deadline, period } =>
+			{
+				attributes.sched_policy = SCHED_DEADLINE as u32;
+				attributes.sched_runtime = nanoseconds(runtime)?;
+				attributes.sched_deadline = nanoseconds(deadline)?;
+				attributes.sched_period = nanoseconds(period)?;
+			}
+
+			Unknown(policy) => attributes.sched_policy = policy,
+		}
+
+		Ok(attributes)
+	}
+
+	#[inline(always)]
+	fn from_sched_attr(attributes: &sched_attr) -> Self
+	{
+		use self::SchedulingPolicy::*;
+
+		let priority = attributes.sched_priority as u8;
+		match attributes.sched_policy as c_int
+		{
+			SCHED_OTHER => Other { nice: attributes.sched_nice as i8 },
+			SCHED_BATCH => Batch { nice: attributes.sched_nice as i8 },
+			SCHED_IDLE => Idle,
+			SCHED_FIFO => Fifo(priority),
+			SCHED_RR => RoundRobin(priority),
+			SCHED_DEADLINE => Deadline { runtime: Duration::from_nanos(attributes.sched_runtime), deadline: Duration::from_nanos(attributes.sched_deadline), period: Duration::from_nanos(attributes.sched_period) },
+			_ => Unknown(attributes.sched_policy),
+		}
+	}
+}
+
+#[cfg(test)]
+mod scheduling_policy_tests
+{
+	use super::*;
+
+	#[test]
+	fn sched_attr_round_trips()
+	{
+		use self::SchedulingPolicy::*;
+
+		for &scheduling_policy in &[Other { nice: 0 }, Other { nice: -20 }, Other { nice: 19 }, Batch { nice: 0 }, Batch { nice: 5 }, Idle, Fifo(1), Fifo(99), RoundRobin(50), Deadline { runtime: Duration::from_micros(10), deadline: Duration::from_micros(100), period: Duration::from_millis(1) }, Unknown(7)]
+		{
+			assert_eq!(SchedulingPolicy::from_sched_attr(&scheduling_policy.to_sched_attr().unwrap()), scheduling_policy);
+		}
+	}
+
+	#[test]
+	fn to_sched_attr_rejects_too_large_durations()
+	{
+		let scheduling_policy = SchedulingPolicy::Deadline { runtime: Duration::from_secs(u64::MAX), deadline: Duration::from_secs(u64::MAX), period: Duration::from_secs(u64::MAX) };
+
+		assert_eq!(scheduling_policy.to_sched_attr().unwrap_err().kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn set_for_thread_rejects_nice()
+	{
+		let thread_identifier = unsafe { pthread_self() };
+
+		assert_eq!(SchedulingPolicy::Other { nice: 1 }.set_for_thread(thread_identifier).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(SchedulingPolicy::Batch { nice: 1 }.set_for_thread(thread_identifier).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn set_batch_for_current_thread()
+	{
+		::std::thread::spawn(||
+		{
+			SchedulingPolicy::Batch { nice: 5 }.set_for_current_thread().unwrap();
+
+			assert_eq!(SchedulingPolicy::for_current_thread().unwrap(), SchedulingPolicy::Batch { nice: 5 });
+			assert_eq!(SchedulingPolicy::for_thread(unsafe { pthread_self() }).unwrap(), SchedulingPolicy::Batch { nice: 0 });
+		}).join().unwrap();
+	}
+
+	#[test]
+	fn set_idle_for_current_thread()
+	{
+		::std::thread::spawn(||
+		{
+			SchedulingPolicy::Idle.set_for_current_thread().unwrap();
+
+			assert_eq!(SchedulingPolicy::for_current_thread().unwrap(), SchedulingPolicy::Idle);
+			assert_eq!(SchedulingPolicy::for_thread(unsafe { pthread_self() }).unwrap(), SchedulingPolicy::Idle);
+		}).join().unwrap();
+	}
+}
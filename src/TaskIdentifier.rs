@@ -0,0 +1,8 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A Linux task identifier, ie the kernel's identifier for a thread (`tid`), as returned by `gettid()` and listed in `/proc/<pid>/task`.
+///
+/// Not the same as a `ThreadIdentifier`, which is a `pthread_t`.
+#[cfg(any(target_os = "android", target_os = "linux"))] pub type TaskIdentifier = pid_t;
@@ -0,0 +1,188 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Migration and scheduling statistics for a thread, from `/proc/self/task/<tid>/sched` and `/proc/self/task/<tid>/schedstat`.
+///
+/// Statistics are cumulative since the thread started; use `since()` to find the change over an interval, eg to confirm that pinning a thread has stopped it migrating.
+/// Complements `LogicalCores::current_logical_core()`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThreadSchedStats
+{
+	/// Number of times the thread has migrated from one logical core to another (`se.nr_migrations` in `sched`).
+	pub migrations: u64,
+
+	/// Number of times the thread gave up its logical core, eg to wait for input or output (`nr_voluntary_switches` in `sched`).
+	pub voluntary_context_switches: u64,
+
+	/// Number of times the thread was preempted (`nr_involuntary_switches` in `sched`).
+	pub involuntary_context_switches: u64,
+
+	/// Time spent running on a logical core (first field of `schedstat`).
+	pub time_on_logical_core: Duration,
+
+	/// Time spent runnable but waiting for a logical core (second field of `schedstat`).
+	pub run_delay: Duration,
+
+	/// Number of timeslices run on a logical core (third field of `schedstat`).
+	pub timeslices: u64,
+}
+
+impl ThreadSchedStats
+{
+	/// Statistics for the current thread.
+	#[inline(always)]
+	pub fn for_current_thread() -> io::Result<Self>
+	{
+		Self::from_folder_path(PathBuf::from("/proc/thread-self"))
+	}
+
+	/// Statistics for a thread of the current process identified by its Linux task identifier (`tid`).
+	///
+	/// Fails with `NotFound` if the thread does not exist (eg it has exited).
+	#[inline(always)]
+	pub fn for_task(task_identifier: TaskIdentifier) -> io::Result<Self>
+	{
+		Self::from_folder_path(PathBuf::from(format!("/proc/self/task/{}", task_identifier)))
+	}
+
+	/// The change in statistics since `previous`, which should have been obtained earlier for the same thread.
+	#[inline(always)]
+	pub fn since(&self, previous: &Self) -> Self
+	{
+		Self
+		{
+			migrations: self.migrations.saturating_sub(previous.migrations),
+			voluntary_context_switches: self.voluntary_context_switches.saturating_sub(previous.voluntary_context_switches),
+			involuntary_context_switches: self.involuntary_context_switches.saturating_sub(previous.involuntary_context_switches),
+			time_on_logical_core: self.time_on_logical_core.checked_sub(previous.time_on_logical_core).unwrap_or_default(),
+			run_delay: self.run_delay.checked_sub(previous.run_delay).unwrap_or_default(),
+			timeslices: self.timeslices.saturating_sub(previous.timeslices),
+		}
+	}
+
+	#[inline(always)]
+	fn from_folder_path(folder_path: PathBuf) -> io::Result<Self>
+	{
+		let sched = read_to_string(folder_path.join("sched"))?;
+		let schedstat = read_to_string(folder_path.join("schedstat"))?;
+		Self::parse(&sched, &schedstat)
+	}
+
+	/// Parses the contents of the files `sched` and `schedstat`.
+	fn parse(sched: &str, schedstat: &str) -> io::Result<Self>
+	{
+		#[inline(always)]
+		fn parse_u64(name: &str, value: &str) -> io::Result<u64>
+		{
+			u64::from_str(value).map_err(|_| LogicalCores::invalid_data(format!("Invalid {} '{}'", name, value)))
+		}
+
+		let mut migrations = None;
+		let mut voluntary_context_switches = None;
+		let mut involuntary_context_switches = None;
+		for line in sched.lines()
+		{
+			let mut name_and_value = line.splitn(2, ':');
+			let name = name_and_value.next().unwrap().trim();
+			let value = match name_and_value.next()
+			{
+				None => continue,
+				Some(value) => value.trim(),
+			};
+
+			match name
+			{
+				"se.nr_migrations" => migrations = Some(parse_u64(name, value)?),
+				"nr_voluntary_switches" => voluntary_context_switches = Some(parse_u64(name, value)?),
+				"nr_involuntary_switches" => involuntary_context_switches = Some(parse_u64(name, value)?),
+				_ => (),
+			}
+		}
+
+		let mut fields = schedstat.split_whitespace();
+		let mut next_field = |name: &str| match fields.next()
+		{
+			None => Err(LogicalCores::invalid_data(format!("schedstat lacks {}", name))),
+			Some(value) => parse_u64(name, value),
+		};
+		let time_on_logical_core = Duration::from_nanos(next_field("time on logical core")?);
+		let run_delay = Duration::from_nanos(next_field("run delay")?);
+		let timeslices = next_field("timeslices")?;
+
+		Ok
+		(
+			Self
+			{
+				migrations: migrations.ok_or_else(|| LogicalCores::invalid_data("sched lacks se.nr_migrations".to_string()))?,
+				voluntary_context_switches: voluntary_context_switches.ok_or_else(|| LogicalCores::invalid_data("sched lacks nr_voluntary_switches".to_string()))?,
+				involuntary_context_switches: involuntary_context_switches.ok_or_else(|| LogicalCores::invalid_data("sched lacks nr_involuntary_switches".to_string()))?,
+				time_on_logical_core,
+				run_delay,
+				timeslices,
+			}
+		)
+	}
+}
+
+#[cfg(test)]
+mod thread_sched_stats_tests
+{
+	use super::*;
+
+	const Sched: &str = "worker (1234, #threads: 4)\n-------------------------------------------------------------------\nse.exec_start                                :       5647836.870586\nse.nr_migrations                             :                    7\nnr_switches                                  :                   12\nnr_voluntary_switches                        :                   10\nnr_involuntary_switches                      :                    2\npolicy                                       :                    0\ncurrent_node=0, numa_group_id=0\n";
+
+	const Schedstat: &str = "1500000 250000 12\n";
+
+	#[test]
+	fn parse()
+	{
+		assert_eq!
+		(
+			ThreadSchedStats::parse(Sched, Schedstat).unwrap(),
+			ThreadSchedStats
+			{
+				migrations: 7,
+				voluntary_context_switches: 10,
+				involuntary_context_switches: 2,
+				time_on_logical_core: Duration::from_micros(1500),
+				run_delay: Duration::from_micros(250),
+				timeslices: 12,
+			}
+		);
+	}
+
+	#[test]
+	fn parse_rejects_missing_or_invalid_fields()
+	{
+		let without_migrations = Sched.replace("se.nr_migrations", "se.nr_wakeups");
+
+		assert_eq!(ThreadSchedStats::parse(&without_migrations, Schedstat).unwrap_err().kind(), io::ErrorKind::InvalidData);
+		assert_eq!(ThreadSchedStats::parse(&Sched.replace(":                    2", ":                   -2"), Schedstat).unwrap_err().kind(), io::ErrorKind::InvalidData);
+		assert_eq!(ThreadSchedStats::parse(Sched, "1500000 250000\n").unwrap_err().kind(), io::ErrorKind::InvalidData);
+		assert_eq!(ThreadSchedStats::parse(Sched, "1500000 x 12\n").unwrap_err().kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn since()
+	{
+		let previous = ThreadSchedStats::parse(Sched, Schedstat).unwrap();
+		let current = ThreadSchedStats { migrations: 8, voluntary_context_switches: 15, timeslices: 20, time_on_logical_core: Duration::from_micros(2000), .. previous };
+
+		assert_eq!(current.since(&previous), ThreadSchedStats { migrations: 1, voluntary_context_switches: 5, involuntary_context_switches: 0, time_on_logical_core: Duration::from_micros(500), run_delay: Duration::from_secs(0), timeslices: 8 });
+		assert_eq!(previous.since(&current), ThreadSchedStats::default());
+	}
+
+	#[test]
+	fn for_current_thread()
+	{
+		::std::thread::spawn(||
+		{
+			let previous = ThreadSchedStats::for_current_thread().unwrap();
+			::std::thread::sleep(Duration::from_millis(1));
+			let current = ThreadSchedStats::for_current_thread().unwrap();
+
+			assert!(current.since(&previous).voluntary_context_switches >= 1);
+		}).join().unwrap();
+	}
+}
@@ -0,0 +1,64 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Selects threads of a process for a `PlacementAudit`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ThreadSelector
+{
+	/// All threads with this name (`comm`); names are at most 15 bytes, so a longer name is truncated to 15 bytes before matching, as the kernel does when a thread's name is set.
+	Name(String),
+
+	/// The thread with this Linux task identifier (`tid`).
+	TaskIdentifier(TaskIdentifier),
+}
+
+impl Display for ThreadSelector
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::ThreadSelector::*;
+
+		match *self
+		{
+			Name(ref name) => write!(f, "thread named '{}'", name),
+			TaskIdentifier(task_identifier) => write!(f, "thread {}", task_identifier),
+		}
+	}
+}
+
+impl ThreadSelector
+{
+	/// Does this select `process_thread`?
+	#[inline(always)]
+	pub fn selects(&self, process_thread: &ProcessThread) -> bool
+	{
+		use self::ThreadSelector::*;
+
+		match *self
+		{
+			Name(ref name) => process_thread.name == Self::truncate_name(name),
+			TaskIdentifier(task_identifier) => process_thread.task_identifier == task_identifier,
+		}
+	}
+
+	/// Thread names (`comm`) are at most 15 bytes (`TASK_COMM_LEN` less a trailing nul); truncates at a character boundary.
+	#[inline(always)]
+	fn truncate_name(name: &str) -> &str
+	{
+		const MaximumNameLength: usize = 15;
+
+		if name.len() <= MaximumNameLength
+		{
+			return name
+		}
+
+		let mut length = MaximumNameLength;
+		while !name.is_char_boundary(length)
+		{
+			length -= 1;
+		}
+		&name[ .. length]
+	}
+}
@@ -0,0 +1,124 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// A snapshot of the topology of a set of logical cores.
+///
+/// Logical cores not in the snapshot are treated as having an unknown topology (see `LogicalCoreTopology::unknown()`).
+///
+/// Create using `discover()` or, for machines other than the current one, one of the `From` implementations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Topology(PerLogicalCoreData<LogicalCoreTopology>);
+
+impl From<PerLogicalCoreData<LogicalCoreTopology>> for Topology
+{
+	#[inline(always)]
+	fn from(logical_cores_topology: PerLogicalCoreData<LogicalCoreTopology>) -> Self
+	{
+		Self(logical_cores_topology)
+	}
+}
+
+impl From<Topology> for PerLogicalCoreData<LogicalCoreTopology>
+{
+	#[inline(always)]
+	fn from(topology: Topology) -> Self
+	{
+		topology.0
+	}
+}
+
+impl Deref for Topology
+{
+	type Target = PerLogicalCoreData<LogicalCoreTopology>;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target
+	{
+		&self.0
+	}
+}
+
+impl Topology
+{
+	/// Discovers the topology of `logical_cores`.
+	///
+	/// Slow as it will read several files in `/sys/devices/system/cpu` for each logical core.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	#[inline(always)]
+	pub fn discover(logical_cores: &LogicalCores) -> Self
+	{
+		Self(PerLogicalCoreData::new(logical_cores, LogicalCoreTopology::discover))
+	}
+
+	/// Topology of a logical core.
+	#[inline(always)]
+	pub fn logical_core_topology(&self, logical_core_identifier: LogicalCoreIdentifier) -> LogicalCoreTopology
+	{
+		match self.0.get(logical_core_identifier)
+		{
+			None => LogicalCoreTopology::unknown(logical_core_identifier),
+			Some(logical_core_topology) => *logical_core_topology,
+		}
+	}
+
+	/// Groups `logical_cores` by package.
+	#[inline(always)]
+	pub fn packages(&self, logical_cores: &LogicalCores) -> BTreeMap<PackageIdentifier, LogicalCores>
+	{
+		self.group_by(logical_cores, |logical_core_topology| logical_core_topology.package)
+	}
+
+	/// Groups `logical_cores` by NUMA node.
+	#[inline(always)]
+	pub fn numa_nodes(&self, logical_cores: &LogicalCores) -> BTreeMap<NumaNodeIdentifier, LogicalCores>
+	{
+		self.group_by(logical_cores, |logical_core_topology| logical_core_topology.numa_node)
+	}
+
+	/// Groups `logical_cores` by last level cache.
+	///
+	/// Each last level cache is identified by the lowest logical core which shares it.
+	#[inline(always)]
+	pub fn last_level_caches(&self, logical_cores: &LogicalCores) -> BTreeMap<LogicalCoreIdentifier, LogicalCores>
+	{
+		self.group_by(logical_cores, |logical_core_topology| logical_core_topology.last_level_cache)
+	}
+
+	/// Groups `logical_cores` by physical core, ie into simultaneous multi-threading (SMT) siblings.
+	///
+	/// Physical cores are in topological order (by package, then NUMA node, then last level cache, then physical core).
+	#[inline(always)]
+	pub fn physical_cores(&self, logical_cores: &LogicalCores) -> BTreeMap<LogicalCoreTopology, LogicalCores>
+	{
+		self.group_by(logical_cores, |logical_core_topology| logical_core_topology)
+	}
+
+	/// Finds the logical core in `candidates` which is nearest to `logical_core_identifier`.
+	///
+	/// Nearest is, in order of preference, a simultaneous multi-threading (SMT) sibling, a logical core sharing the same last level cache, a logical core in the same NUMA node and then a logical core in the same package; ties are broken by the smallest difference in logical core identifier.
+	///
+	/// Returns `None` if `candidates` is empty.
+	#[inline(always)]
+	pub fn nearest_logical_core(&self, logical_core_identifier: LogicalCoreIdentifier, candidates: impl IntoIterator<Item=LogicalCoreIdentifier>) -> Option<LogicalCoreIdentifier>
+	{
+		let topology = self.logical_core_topology(logical_core_identifier);
+		candidates.into_iter().min_by_key(|&candidate|
+		{
+			let candidate_topology = self.logical_core_topology(candidate);
+			(candidate_topology != topology, candidate_topology.last_level_cache != topology.last_level_cache, candidate_topology.numa_node != topology.numa_node, candidate_topology.package != topology.package, candidate.abs_diff(logical_core_identifier))
+		})
+	}
+
+	#[inline(always)]
+	fn group_by<Key: Ord>(&self, logical_cores: &LogicalCores, key: impl Fn(LogicalCoreTopology) -> Key) -> BTreeMap<Key, LogicalCores>
+	{
+		let mut groups = BTreeMap::new();
+		for logical_core_identifier in logical_cores.iter()
+		{
+			let logical_core_identifier = *logical_core_identifier;
+			groups.entry(key(self.logical_core_topology(logical_core_identifier))).or_insert_with(|| LogicalCores::from(BTreeSet::<LogicalCoreIdentifier>::new())).insert(logical_core_identifier);
+		}
+		groups
+	}
+}
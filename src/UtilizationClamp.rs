@@ -0,0 +1,150 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// Utilization clamping (uclamp) for a thread, a hint to the scheduler and frequency governor of the performance a thread needs, independent of its scheduling policy.
+///
+/// On heterogeneous (eg big.LITTLE) or frequency-scaled systems, a high `minimum` hints that a pinned worker needs high performance, so that it is placed on a high capacity logical core and that logical core runs at a high frequency even when the thread is not busy.
+///
+/// Values are on a scale of 0 to `Scale` (1024), which is the capacity of the most capable logical core at its highest frequency.
+///
+/// Requires Linux 5.3 or later built with `CONFIG_UCLAMP_TASK`; otherwise `UtilizationClampError::Unsupported` is returned, rather than silently doing nothing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UtilizationClamp
+{
+	/// Minimum utilization, from 0 to `Scale`.
+	pub minimum: u16,
+
+	/// Maximum utilization, from 0 to `Scale`.
+	pub maximum: u16,
+}
+
+impl Default for UtilizationClamp
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self::Unclamped
+	}
+}
+
+impl UtilizationClamp
+{
+	/// The maximum value of utilization, `SCHED_CAPACITY_SCALE`.
+	pub const Scale: u16 = 1024;
+
+	/// No clamping; the default for a thread.
+	pub const Unclamped: Self = Self { minimum: 0, maximum: Self::Scale };
+
+	/// Always treat the thread as needing full performance.
+	pub const HighPerformance: Self = Self { minimum: Self::Scale, maximum: Self::Scale };
+
+	/// Sets the utilization clamp of the current thread, without changing its scheduling policy.
+	#[inline(always)]
+	pub fn set_for_current_thread(&self) -> Result<(), UtilizationClampError>
+	{
+		self.set_for_task(0)
+	}
+
+	/// Sets the utilization clamp of a thread identified by its Linux task identifier (`tid`), without changing its scheduling policy; `0` is the current thread.
+	pub fn set_for_task(&self, task_identifier: TaskIdentifier) -> Result<(), UtilizationClampError>
+	{
+		let (minimum, maximum) = (self.minimum, self.maximum);
+		if unlikely!(minimum > maximum || maximum > Self::Scale)
+		{
+			return Err(UtilizationClampError::InvalidValue { minimum, maximum })
+		}
+
+		// `sched_setattr()` validates the priority and deadline parameters against the thread's policy even with `SCHED_FLAG_KEEP_PARAMS`, so they must be those the thread already has.
+		let mut attributes = SchedulingPolicy::get_attributes(task_identifier)?;
+
+		// Kernels before Linux 5.3 report a smaller `sched_attr`; they would fail with `EINVAL` rather than `E2BIG` for a minimum and maximum of 0, as they do not know the utilization clamp flags.
+		if unlikely!(attributes.size < SCHED_ATTR_SIZE_VER1)
+		{
+			return Err(UtilizationClampError::Unsupported)
+		}
+
+		attributes.size = size_of::<sched_attr>() as u32;
+		attributes.sched_flags = SCHED_FLAG_KEEP_POLICY | SCHED_FLAG_KEEP_PARAMS | SCHED_FLAG_UTIL_CLAMP_MIN | SCHED_FLAG_UTIL_CLAMP_MAX;
+		attributes.sched_util_min = minimum as u32;
+		attributes.sched_util_max = maximum as u32;
+
+		match SchedulingPolicy::set_attributes(task_identifier, &attributes)
+		{
+			Ok(()) => Ok(()),
+
+			// Kernels built without `CONFIG_UCLAMP_TASK` fail with `EOPNOTSUPP`; `E2BIG` is handled too in case a kernel before Linux 5.3 reports the larger size of `sched_attr`.
+			Err(ref error) if error.raw_os_error() == Some(EOPNOTSUPP) || error.raw_os_error() == Some(E2BIG) => Err(UtilizationClampError::Unsupported),
+
+			Err(error) => Err(UtilizationClampError::Io(error)),
+		}
+	}
+
+	/// Gets the utilization clamp of the current thread.
+	#[inline(always)]
+	pub fn for_current_thread() -> Result<Self, UtilizationClampError>
+	{
+		Self::for_task(0)
+	}
+
+	/// Gets the utilization clamp of a thread identified by its Linux task identifier (`tid`); `0` is the current thread.
+	///
+	/// A kernel built without `CONFIG_UCLAMP_TASK` reports a minimum and maximum of 0, so in that case the existence of `/proc/sys/kernel/sched_util_clamp_max` is checked.
+	pub fn for_task(task_identifier: TaskIdentifier) -> Result<Self, UtilizationClampError>
+	{
+		let attributes = SchedulingPolicy::get_attributes(task_identifier)?;
+		if unlikely!(attributes.size < SCHED_ATTR_SIZE_VER1)
+		{
+			return Err(UtilizationClampError::Unsupported)
+		}
+		if unlikely!(attributes.sched_util_min == 0 && attributes.sched_util_max == 0 && !Path::new("/proc/sys/kernel/sched_util_clamp_max").exists())
+		{
+			return Err(UtilizationClampError::Unsupported)
+		}
+
+		Ok
+		(
+			Self
+			{
+				minimum: attributes.sched_util_min as u16,
+				maximum: attributes.sched_util_max as u16,
+			}
+		)
+	}
+}
+
+#[cfg(test)]
+mod utilization_clamp_tests
+{
+	use super::*;
+
+	#[test]
+	fn set_for_current_thread_rejects_invalid_values()
+	{
+		for &(minimum, maximum) in &[(1, 0), (UtilizationClamp::Scale, UtilizationClamp::Scale - 1), (0, UtilizationClamp::Scale + 1), (UtilizationClamp::Scale + 1, UtilizationClamp::Scale + 1)]
+		{
+			match (UtilizationClamp { minimum, maximum }).set_for_current_thread()
+			{
+				Err(UtilizationClampError::InvalidValue { minimum: error_minimum, maximum: error_maximum }) => assert_eq!((error_minimum, error_maximum), (minimum, maximum)),
+				result => panic!("Expected InvalidValue for {} and {}, not {:?}", minimum, maximum, result),
+			}
+		}
+	}
+
+	#[test]
+	fn set_for_current_thread_accepts_valid_values_or_is_unsupported()
+	{
+		::std::thread::spawn(||
+		{
+			for &utilization_clamp in &[UtilizationClamp { minimum: 0, maximum: 0 }, UtilizationClamp { minimum: 512, maximum: 768 }, UtilizationClamp::HighPerformance, UtilizationClamp::Unclamped]
+			{
+				match utilization_clamp.set_for_current_thread()
+				{
+					Ok(()) => assert_eq!(UtilizationClamp::for_current_thread().unwrap(), utilization_clamp),
+					Err(UtilizationClampError::Unsupported) => (),
+					Err(error) => panic!("Unexpected error {:?} for {:?}", error, utilization_clamp),
+				}
+			}
+		}).join().unwrap();
+	}
+}
@@ -0,0 +1,64 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+/// An error getting or setting a `UtilizationClamp`.
+#[derive(Debug)]
+pub enum UtilizationClampError
+{
+	/// The kernel does not support utilization clamping, either because it is older than Linux 5.3 or because it was built without `CONFIG_UCLAMP_TASK`.
+	Unsupported,
+
+	/// A minimum or maximum was greater than `UtilizationClamp::Scale`, or the minimum was greater than the maximum.
+	InvalidValue
+	{
+		/// Minimum.
+		minimum: u16,
+
+		/// Maximum.
+		maximum: u16,
+	},
+
+	/// Any other error, eg the thread does not exist (`ESRCH`) or permission was denied (`EPERM`).
+	Io(io::Error),
+}
+
+impl Display for UtilizationClampError
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		use self::UtilizationClampError::*;
+
+		match *self
+		{
+			Unsupported => write!(f, "Utilization clamping is not supported by the kernel"),
+			InvalidValue { minimum, maximum } => write!(f, "Utilization clamp minimum {} and maximum {} are invalid", minimum, maximum),
+			Io(ref error) => write!(f, "{}", error),
+		}
+	}
+}
+
+impl error::Error for UtilizationClampError
+{
+	#[inline(always)]
+	fn source(&self) -> Option<&(dyn error::Error + 'static)>
+	{
+		use self::UtilizationClampError::*;
+
+		match *self
+		{
+			Io(ref error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for UtilizationClampError
+{
+	#[inline(always)]
+	fn from(error: io::Error) -> Self
+	{
+		UtilizationClampError::Io(error)
+	}
+}
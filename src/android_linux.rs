@@ -0,0 +1,182 @@
+// This file is part of cpu-affinity. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT. No part of cpu-affinity, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of cpu-affinity. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/cpu-affinity/master/COPYRIGHT.
+
+
+use ::libc::c_int;
+use ::libc::c_long;
+use ::libc::c_uint;
+use ::libc::c_ulong;
+use ::libc::c_void;
+use ::libc::pid_t;
+use ::libc::syscall;
+use ::libc::SYS_get_mempolicy;
+use ::libc::SYS_mbind;
+use ::libc::SYS_sched_getattr;
+use ::libc::SYS_sched_setattr;
+use ::libc::SYS_set_mempolicy;
+use super::ClassicBpfInstruction;
+
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_DEFAULT: c_int = 0;
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_PREFERRED: c_int = 1;
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_BIND: c_int = 2;
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_INTERLEAVE: c_int = 3;
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_LOCAL: c_int = 4;
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_F_NUMA_BALANCING: c_int = 1 << 13;
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_F_RELATIVE_NODES: c_int = 1 << 14;
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_F_STATIC_NODES: c_int = 1 << 15;
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_MODE_FLAGS: c_int = MPOL_F_STATIC_NODES | MPOL_F_RELATIVE_NODES | MPOL_F_NUMA_BALANCING;
+
+/// Defined in `include/uapi/linux/mempolicy.h` in Linux source code.
+pub(crate) const MPOL_MF_MOVE: c_uint = 1 << 1;
+
+/// Defined in `include/uapi/asm-generic/socket.h` in Linux source code.
+#[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))] pub(crate) const SO_INCOMING_CPU: c_int = 49;
+
+/// Defined in `arch/sparc/include/uapi/asm/socket.h` in Linux source code.
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))] pub(crate) const SO_INCOMING_CPU: c_int = 0x0033;
+
+/// Defined in `include/uapi/asm-generic/socket.h` in Linux source code.
+#[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))] pub(crate) const SO_ATTACH_REUSEPORT_CBPF: c_int = 51;
+
+/// Defined in `arch/sparc/include/uapi/asm/socket.h` in Linux source code.
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))] pub(crate) const SO_ATTACH_REUSEPORT_CBPF: c_int = 0x0035;
+
+/// Defined in `include/uapi/linux/bpf_common.h` in Linux source code.
+pub(crate) const BPF_LD: u16 = 0x00;
+
+/// Defined in `include/uapi/linux/bpf_common.h` in Linux source code.
+pub(crate) const BPF_ALU: u16 = 0x04;
+
+/// Defined in `include/uapi/linux/bpf_common.h` in Linux source code.
+pub(crate) const BPF_JMP: u16 = 0x05;
+
+/// Defined in `include/uapi/linux/bpf_common.h` in Linux source code.
+pub(crate) const BPF_RET: u16 = 0x06;
+
+/// Defined in `include/uapi/linux/bpf_common.h` in Linux source code.
+pub(crate) const BPF_W: u16 = 0x00;
+
+/// Defined in `include/uapi/linux/bpf_common.h` in Linux source code.
+pub(crate) const BPF_ABS: u16 = 0x20;
+
+/// Defined in `include/uapi/linux/bpf_common.h` in Linux source code.
+pub(crate) const BPF_SUB: u16 = 0x10;
+
+/// Defined in `include/uapi/linux/bpf_common.h` in Linux source code.
+pub(crate) const BPF_JEQ: u16 = 0x10;
+
+/// Defined in `include/uapi/linux/bpf_common.h` in Linux source code.
+pub(crate) const BPF_K: u16 = 0x00;
+
+/// Defined in `include/uapi/linux/filter.h` in Linux source code.
+pub(crate) const BPF_A: u16 = 0x10;
+
+/// Defined in `include/uapi/linux/filter.h` in Linux source code.
+pub(crate) const BPF_MAXINSNS: usize = 4096;
+
+/// Defined in `include/uapi/linux/filter.h` in Linux source code.
+pub(crate) const SKF_AD_OFF: i32 = -0x1000;
+
+/// Defined in `include/uapi/linux/filter.h` in Linux source code.
+pub(crate) const SKF_AD_CPU: i32 = 36;
+
+/// Defined in `include/uapi/linux/filter.h` in Linux source code.
+#[repr(C)]
+pub(crate) struct sock_fprog
+{
+	pub(crate) len: u16,
+	pub(crate) filter: *const ClassicBpfInstruction,
+}
+
+/// Defined in `include/uapi/linux/sched.h` in Linux source code.
+pub(crate) const SCHED_DEADLINE: c_int = 6;
+
+/// Defined in `include/uapi/linux/sched.h` in Linux source code.
+pub(crate) const SCHED_RESET_ON_FORK: c_int = 0x40000000;
+
+/// Defined in `include/uapi/linux/sched.h` in Linux source code.
+pub(crate) const SCHED_FLAG_KEEP_POLICY: u64 = 0x08;
+
+/// Defined in `include/uapi/linux/sched.h` in Linux source code.
+pub(crate) const SCHED_FLAG_KEEP_PARAMS: u64 = 0x10;
+
+/// Defined in `include/uapi/linux/sched.h` in Linux source code.
+pub(crate) const SCHED_FLAG_UTIL_CLAMP_MIN: u64 = 0x20;
+
+/// Defined in `include/uapi/linux/sched.h` in Linux source code.
+pub(crate) const SCHED_FLAG_UTIL_CLAMP_MAX: u64 = 0x40;
+
+/// Defined in `include/uapi/linux/sched/types.h` in Linux source code.
+pub(crate) const SCHED_ATTR_SIZE_VER1: u32 = 56;
+
+/// Defined in `include/uapi/linux/sched/types.h` in Linux source code.
+///
+/// Includes the utilization clamping fields added in Linux 5.3 (`SCHED_ATTR_SIZE_VER1`); older kernels accept this size as long as they are zero.
+#[repr(C)]
+#[derive(Default, Debug, Copy, Clone)]
+pub(crate) struct sched_attr
+{
+	pub(crate) size: u32,
+	pub(crate) sched_policy: u32,
+	pub(crate) sched_flags: u64,
+	pub(crate) sched_nice: i32,
+	pub(crate) sched_priority: u32,
+	pub(crate) sched_runtime: u64,
+	pub(crate) sched_deadline: u64,
+	pub(crate) sched_period: u64,
+	pub(crate) sched_util_min: u32,
+	pub(crate) sched_util_max: u32,
+}
+
+/// Defined in `include/linux/syscalls.h` in Linux source code; not wrapped by glibc or musl.
+#[inline(always)]
+pub(crate) unsafe fn sched_setattr(pid: pid_t, attr: *const sched_attr, flags: c_uint) -> c_long
+{
+	syscall(SYS_sched_setattr, pid, attr, flags)
+}
+
+/// Defined in `include/linux/syscalls.h` in Linux source code; not wrapped by glibc or musl.
+#[inline(always)]
+pub(crate) unsafe fn sched_getattr(pid: pid_t, attr: *mut sched_attr, size: c_uint, flags: c_uint) -> c_long
+{
+	syscall(SYS_sched_getattr, pid, attr, size, flags)
+}
+
+/// Defined in `include/linux/syscalls.h` in Linux source code; not wrapped by glibc or musl.
+#[inline(always)]
+pub(crate) unsafe fn mbind(start: *mut c_void, len: c_ulong, mode: c_int, nmask: *const c_ulong, maxnode: c_ulong, flags: c_uint) -> c_long
+{
+	syscall(SYS_mbind, start, len, mode, nmask, maxnode, flags)
+}
+
+/// Defined in `include/linux/syscalls.h` in Linux source code; not wrapped by glibc or musl.
+#[inline(always)]
+pub(crate) unsafe fn set_mempolicy(mode: c_int, nmask: *const c_ulong, maxnode: c_ulong) -> c_long
+{
+	syscall(SYS_set_mempolicy, mode, nmask, maxnode)
+}
+
+/// Defined in `include/linux/syscalls.h` in Linux source code; not wrapped by glibc or musl.
+#[inline(always)]
+pub(crate) unsafe fn get_mempolicy(policy: *mut c_int, nmask: *mut c_ulong, maxnode: c_ulong, addr: *mut c_void, flags: c_ulong) -> c_long
+{
+	syscall(SYS_get_mempolicy, policy, nmask, maxnode, addr, flags)
+}
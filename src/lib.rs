@@ -34,23 +34,117 @@
 #[cfg(windows)] extern crate winapi;
 
 
+#[cfg(any(target_os = "android", target_os = "linux"))] use self::android_linux::*;
 #[cfg(any(target_os = "android", target_os = "linux"))] use ::dpdk_unix::ProcPath;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::dpdk_unix::SysPath;
 #[cfg(any(target_os = "android", target_os = "linux"))] use ::dpdk_unix::scheduling::CpuSet;
 #[cfg(any(target_os = "android", target_os = "linux"))] use ::dpdk_unix::hyper_thread::HyperThread;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::_SC_PAGESIZE;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::AF_NETLINK;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::bind;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::c_int;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::c_uint;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::c_ulong;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::c_void;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::close;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::EAGAIN;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::EINTR;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::MAP_ANONYMOUS;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::MAP_FAILED;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::MAP_PRIVATE;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::mmap;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::munmap;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::E2BIG;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::ENOBUFS;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::EOPNOTSUPP;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::ESRCH;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::getsockopt;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::MSG_DONTWAIT;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::NETLINK_KOBJECT_UEVENT;
 #[cfg(unix)] use ::libc::pid_t;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::poll;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::pollfd;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::POLLIN;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::PROT_READ;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::PROT_WRITE;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::pthread_getschedparam;
 #[cfg(unix)] use ::libc::pthread_self;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::pthread_setschedparam;
 #[cfg(unix)] use ::libc::pthread_t;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::recv;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::sa_family_t;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::SCHED_BATCH;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::SCHED_FIFO;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::SCHED_IDLE;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::SCHED_OTHER;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::sched_param;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::SCHED_RR;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::setsockopt;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::SOCK_CLOEXEC;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::SOCK_DGRAM;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::sockaddr;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::sockaddr_nl;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::socket;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::SOL_SOCKET;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::socklen_t;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::libc::sysconf;
 use ::std::borrow::Borrow;
 use ::std::borrow::BorrowMut;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::cmp::max;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::cmp::min;
+use ::std::cmp::Ordering;
+use ::std::collections::BTreeMap;
 use ::std::collections::BTreeSet;
+use ::std::env::var;
+use ::std::error;
+use ::std::fmt;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::fmt::Debug;
+use ::std::fmt::Display;
+use ::std::fmt::Formatter;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::fs::OpenOptions;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::fs::read_dir;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::fs::read_to_string;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::hash::Hash;
+use ::std::iter::Enumerate;
+use ::std::iter::FromIterator;
+use ::std::iter::FusedIterator;
 use ::std::ops::Deref;
 use ::std::ops::DerefMut;
 use ::std::ops::Index;
 use ::std::ops::IndexMut;
 use ::std::io;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::io::Write;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::marker::PhantomData;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::mem::align_of;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::mem::forget;
+use ::std::mem::replace;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::mem::size_of;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::mem::zeroed;
+use ::std::panic::resume_unwind;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::process;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::ptr::drop_in_place;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::ptr::NonNull;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::ptr::null;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::ptr::null_mut;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::ptr::read;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::os::unix::io::RawFd;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::path::Path;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::path::PathBuf;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::ptr::write;
+use ::std::slice;
+use ::std::str::FromStr;
+use ::std::thread::Builder;
+use ::std::thread::scope;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::thread::sleep;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::time::Duration;
+#[cfg(any(target_os = "android", target_os = "linux"))] use ::std::time::Instant;
+use ::std::vec;
 #[cfg(any(target_os = "android", target_os = "linux"))] use ::std::mem::transmute;
 
 
+#[cfg(any(target_os = "android", target_os = "linux"))] pub(crate) mod android_linux;
+
+
 #[cfg(target_os = "dragonfly")] pub(crate) mod dragonfly;
 
 
@@ -66,8 +160,65 @@ use ::std::io;
 #[cfg(target_env = "uclibc")] pub(crate) mod uclibc;
 
 
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("ClassicBpfInstruction.rs");
+include!("CoreUtilisation.rs");
+include!("CpuTimes.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("Domain.rs");
+include!("HousekeepingPolicy.rs");
+include!("Interrupt.rs");
+include!("InterruptRequestNumber.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("IrqAffinity.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("IsolationValidator.rs");
+include!("IsolationWarning.rs");
+include!("LocationExpression.rs");
+include!("LocationExpressionError.rs");
+include!("LocationExpressionLevel.rs");
+include!("LocationIndices.rs");
+include!("LocationObjectType.rs");
 include!("LogicalCores.rs");
+include!("LogicalCoreFrequency.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("LogicalCoreHotplugEvent.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("LogicalCoreHotplugWatcher.rs");
 include!("LogicalCoreIdentifier.rs");
+include!("LogicalCoreTopology.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("MemoryPolicy.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("NumaLocalBox.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("NetworkInterface.rs");
+include!("NetworkInterfaceQueue.rs");
+include!("NetworkInterfaceQueueKind.rs");
+include!("NumaNodeIdentifier.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("NumaNodeDomain.rs");
+include!("NumaNodes.rs");
+include!("OpenMpParseError.rs");
+include!("OpenMpPlaces.rs");
+include!("OpenMpProcessorBinding.rs");
+include!("OpenMpSpecificationCursor.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("PackageDomain.rs");
+include!("PackageIdentifier.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("PerDomainData.rs");
 include!("PerLogicalCoreData.rs");
+include!("PerLogicalCoreDataIntoIterator.rs");
+include!("PerLogicalCoreDataIterator.rs");
+include!("PerLogicalCoreDataMutableIterator.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("PerNumaNodeData.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("PerPackageData.rs");
+include!("PhysicalCoreIdentifier.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("PlacementAudit.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("PlacementDrift.rs");
+include!("PlacementError.rs");
+include!("PlacementPlanner.rs");
+include!("PlacementPolicy.rs");
 include!("ProcessIdentifier.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("ProcessThread.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("ProcessThreads.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("Rebalancer.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("RebalancingMove.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("ReusePortSteeringProgram.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("SchedulingPolicy.rs");
+include!("TaskIdentifier.rs");
 include!("ThreadIdentifier.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("ThreadSchedStats.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("ThreadSelector.rs");
+include!("Topology.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("UtilizationClamp.rs");
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("UtilizationClampError.rs");